@@ -3,6 +3,8 @@ use std::ops::Bound::{Excluded, Included};
 
 use scrypto_avltree::avl_tree::AvlTree;
 use scrypto_avltree::avl_tree::IterMutControl;
+use scrypto_avltree::avl_tree::{BulkDelete, BulkLoad, RangeCursor};
+use scrypto_avltree::avl_tree_error::AvlTreeError;
 use scrypto_avltree::avl_tree_health::{check_health, print_tree_nice};
 use std::ops::RangeBounds;
 fn key_value(tuple: (i32, i32, Option<i32>)) -> (i32, i32) {
@@ -14,18 +16,54 @@ mod avl_test_wrapper {
 
     struct AvlTestWrapper {
         avl_tree: AvlTree<i32, i32>,
+        bulk_load: BulkLoad<i32>,
+        bulk_delete: BulkDelete<i32>,
+        page_cursor: Option<RangeCursor<i32>>,
+        split_trees: Vec<AvlTree<i32, i32>>,
     }
 
     impl AvlTestWrapper {
         pub fn instantiate() -> Global<AvlTestWrapper> {
             let avl_tree = AvlTree::default();
-            let component = (Self { avl_tree })
-                .instantiate()
-                .prepare_to_globalize(OwnerRole::None)
-                .globalize();
+            let component = (Self {
+                avl_tree,
+                bulk_load: BulkLoad::new(),
+                bulk_delete: BulkDelete::new(),
+                page_cursor: None,
+                split_trees: Vec::new(),
+            })
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize();
             component
         }
 
+        pub fn instantiate_with_store() -> Global<AvlTestWrapper> {
+            let store = KeyValueStore::new();
+            let avl_tree = AvlTree::with_store(store);
+            let component = (Self {
+                avl_tree,
+                bulk_load: BulkLoad::new(),
+                bulk_delete: BulkDelete::new(),
+                page_cursor: None,
+                split_trees: Vec::new(),
+            })
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize();
+            component
+        }
+
+        pub fn store_address(&self) -> InternalAddress {
+            let address = self.avl_tree.store_address();
+            assert_eq!(
+                address.as_node_id(),
+                &self.avl_tree.store_node_id(),
+                "Store address differs from the node id"
+            );
+            address
+        }
+
         pub fn insert(&mut self, key: i32, value: i32) -> Option<i32> {
             self.avl_tree.insert(key, value)
         }
@@ -121,6 +159,99 @@ mod avl_test_wrapper {
             self.range_with_range_bounds((Excluded(key1), Excluded(key2)))
         }
 
+        pub fn start_range_page(&mut self, key1: i32, key2: i32, back: bool) {
+            self.page_cursor = Some(match back {
+                true => RangeCursor::new_back(key1..key2),
+                false => RangeCursor::new(key1..key2),
+            });
+        }
+
+        pub fn next_range_page(&mut self, limit: u32) -> (Vec<(i32, i32)>, bool) {
+            let Some(cursor) = self.page_cursor.take() else {
+                return (vec![], false);
+            };
+            let (page, cursor) = self.avl_tree.range_page(cursor, limit as usize);
+            self.page_cursor = cursor;
+            (page, self.page_cursor.is_some())
+        }
+
+        pub fn get_range_rev(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            self.avl_tree
+                .range(key1..key2)
+                .rev()
+                .map(key_value)
+                .collect()
+        }
+
+        pub fn get_range_back_rev(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            self.avl_tree
+                .range_back(key1..key2)
+                .rev()
+                .map(key_value)
+                .collect()
+        }
+
+        pub fn get_range_alternating(
+            &mut self,
+            key1: i32,
+            key2: i32,
+            back_first: bool,
+        ) -> Vec<i32> {
+            let mut iter = self.avl_tree.range(key1..key2);
+            let mut result = Vec::new();
+            let mut from_back = back_first;
+            loop {
+                let item = match from_back {
+                    true => iter.next_back(),
+                    false => iter.next(),
+                };
+                match item {
+                    Some((key, _, _)) => result.push(key),
+                    None => break,
+                }
+                from_back = !from_back;
+            }
+            // Both ends have to stay exhausted after they met.
+            assert!(iter.next().is_none() && iter.next_back().is_none());
+            result
+        }
+
+        pub fn get_range_ref(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            self.avl_tree
+                .range_ref(key1..key2)
+                .map(|(k, v)| (k, *v))
+                .collect()
+        }
+
+        pub fn get_range_back_ref(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            self.avl_tree
+                .range_back_ref(key1..key2)
+                .map(|(k, v)| (k, *v))
+                .collect()
+        }
+
+        pub fn get_range_ref_held(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            // All references are alive at the same time before they are read.
+            let refs: Vec<_> = self.avl_tree.range_ref(key1..key2).collect();
+            refs.iter().map(|(k, v)| (*k, **v)).collect()
+        }
+
+        pub fn get_keys(&mut self, key1: i32, key2: i32) -> Vec<i32> {
+            self.avl_tree.keys(key1..key2).collect()
+        }
+
+        pub fn get_keys_back(&mut self, key1: i32, key2: i32) -> Vec<i32> {
+            self.avl_tree.keys_back(key1..key2).collect()
+        }
+
+        pub fn partition_point_by_key(&mut self, key: i32) -> Option<i32> {
+            self.avl_tree.partition_point(|k, _| *k >= key)
+        }
+
+        pub fn partition_point_by_value(&mut self, threshold: i32) -> Option<i32> {
+            self.avl_tree.partition_point(|_, v| *v > threshold)
+        }
+
         pub fn update_values(&mut self, start_key: i32, end_key: i32, new_value: i32) {
             self.avl_tree.range_mut(start_key..end_key).for_each(
                 |(_, value, _): (&i32, &mut i32, Option<i32>)| {
@@ -173,5 +304,120 @@ mod avl_test_wrapper {
         pub fn remove(&mut self, key: i32) -> Option<i32> {
             self.avl_tree.remove(&key)
         }
+
+        pub fn try_insert(
+            &mut self,
+            key: i32,
+            value: i32,
+        ) -> Result<Option<i32>, AvlTreeError<i32>> {
+            self.avl_tree.try_insert(key, value)
+        }
+
+        pub fn try_remove(&mut self, key: i32) -> Result<Option<i32>, AvlTreeError<i32>> {
+            self.avl_tree.try_remove(&key)
+        }
+
+        pub fn try_update_value(
+            &mut self,
+            key: i32,
+            new_value: i32,
+        ) -> Result<i32, AvlTreeError<i32>> {
+            let mut value = self.avl_tree.try_get_mut(&key)?;
+            Ok(mem::replace(&mut *value, new_value))
+        }
+
+        pub fn try_get_range(
+            &mut self,
+            key1: i32,
+            key2: i32,
+        ) -> Result<Vec<(i32, i32)>, AvlTreeError<i32>> {
+            self.avl_tree
+                .try_range(key1..key2)?
+                .map(|item| item.map(key_value))
+                .collect()
+        }
+
+        pub fn insert_if_absent(&mut self, key: i32, value: i32) -> Result<(), i32> {
+            self.avl_tree.insert_if_absent(key, value)
+        }
+
+        pub fn compare_and_swap(
+            &mut self,
+            key: i32,
+            expected: i32,
+            new_value: i32,
+        ) -> Result<i32, Option<i32>> {
+            self.avl_tree.compare_and_swap(&key, &expected, new_value)
+        }
+
+        pub fn withdraw(&mut self, key: i32, amount: i32) -> Option<i32> {
+            self.avl_tree
+                .update(&key, |value| (*value > amount).then(|| value - amount))
+        }
+
+        pub fn rekey(&mut self, old_key: i32, new_key: i32) -> Result<(), AvlTreeError<i32>> {
+            self.avl_tree.rekey(&old_key, new_key)
+        }
+
+        pub fn insert_with_hint(&mut self, hint: i32, key: i32, value: i32) -> Option<i32> {
+            self.avl_tree.insert_with_hint(&hint, key, value)
+        }
+
+        pub fn get_near(&self, hint: i32, key: i32) -> Option<(i32, i32)> {
+            self.avl_tree
+                .get_near(&hint, &key)
+                .map(|(key, value)| (key, *value))
+        }
+
+        pub fn insert_sorted_batch(&mut self, keys: Vec<i32>) {
+            self.avl_tree
+                .insert_sorted_batch(keys.into_iter().map(|key| (key, key * 10)));
+        }
+
+        pub fn remove_range(&mut self, key1: i32, key2: i32) -> u64 {
+            self.avl_tree.remove_range(key1..key2) as u64
+        }
+
+        pub fn drain_range(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            self.avl_tree.drain_range(key1..key2)
+        }
+
+        pub fn split_off(&mut self, key: i32) -> u64 {
+            let split_tree = self.avl_tree.split_off(&key);
+            self.split_trees.push(split_tree);
+            (self.split_trees.len() - 1) as u64
+        }
+
+        pub fn append_split(&mut self, index: u64) {
+            self.avl_tree.append(&mut self.split_trees[index as usize]);
+        }
+
+        pub fn append_to_split(&mut self, index: u64) {
+            self.split_trees[index as usize].append(&mut self.avl_tree);
+        }
+
+        pub fn check_split_health(&mut self, index: u64) {
+            check_health(&mut self.split_trees[index as usize]);
+        }
+
+        pub fn get_split_range(&mut self, index: u64) -> Vec<(i32, i32)> {
+            let split_tree = &self.split_trees[index as usize];
+            let values: Vec<(i32, i32)> = split_tree.range(..).map(key_value).collect();
+            let mut back: Vec<(i32, i32)> = split_tree.range_back(..).map(key_value).collect();
+            back.reverse();
+            assert_eq!(values, back, "Linked list is not consistent");
+            values
+        }
+
+        pub fn bulk_load(&mut self, start_key: i32, end_key: i32) -> u64 {
+            self.avl_tree
+                .bulk_load(&mut self.bulk_load, (start_key..end_key).map(|i| (i, i)));
+            self.bulk_load.loaded()
+        }
+
+        pub fn bulk_delete(&mut self, limit: u32) -> bool {
+            self.avl_tree
+                .bulk_delete(&mut self.bulk_delete, limit as usize)
+        }
     }
 }
@@ -0,0 +1,167 @@
+use scrypto::prelude::*;
+use std::ops::Bound::{Excluded, Included};
+
+use scrypto_avltree::augmented_avl_tree::{Aggregate, AugmentedAvlTree};
+use scrypto_avltree::avl_tree::Direction;
+use scrypto_avltree::avl_tree_health::check_aggregates;
+
+/// Sum, count and maximum of the values of a subtree.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq)]
+pub struct Summary {
+    sum: Decimal,
+    count: u64,
+    max: Option<Decimal>,
+}
+
+impl Aggregate<i32, Decimal> for Summary {
+    fn empty() -> Self {
+        Summary {
+            sum: Decimal::ZERO,
+            count: 0,
+            max: None,
+        }
+    }
+
+    fn from_entry(_key: &i32, value: &Decimal) -> Self {
+        Summary {
+            sum: *value,
+            count: 1,
+            max: Some(*value),
+        }
+    }
+
+    fn combine(&self, right: &Self) -> Self {
+        Summary {
+            sum: self.sum + right.sum,
+            count: self.count + right.count,
+            max: self.max.max(right.max),
+        }
+    }
+}
+
+/// Sum of the quantities of a subtree.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Quantity(Decimal);
+
+impl Aggregate<i32, Decimal> for Quantity {
+    fn empty() -> Self {
+        Quantity(Decimal::ZERO)
+    }
+
+    fn from_entry(_key: &i32, value: &Decimal) -> Self {
+        Quantity(*value)
+    }
+
+    fn combine(&self, right: &Self) -> Self {
+        Quantity(self.0 + right.0)
+    }
+}
+
+impl std::ops::Sub for Quantity {
+    type Output = Quantity;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Quantity(self.0 - other.0)
+    }
+}
+
+fn to_tuple(summary: Summary) -> (Decimal, u64, Option<Decimal>) {
+    (summary.sum, summary.count, summary.max)
+}
+
+#[blueprint]
+mod avl_test_wrapper_augmented {
+    struct AvlTestWrapperAugmented {
+        avl_tree: AugmentedAvlTree<i32, Decimal, Summary>,
+        order_book: AugmentedAvlTree<i32, Decimal, Quantity>,
+    }
+
+    impl AvlTestWrapperAugmented {
+        pub fn instantiate() -> Global<AvlTestWrapperAugmented> {
+            let avl_tree = AugmentedAvlTree::new();
+            let order_book = AugmentedAvlTree::new();
+            let component = (Self {
+                avl_tree,
+                order_book,
+            })
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize();
+            component
+        }
+
+        pub fn insert(&mut self, key: i32, value: Decimal) -> Option<Decimal> {
+            self.avl_tree.insert(key, value)
+        }
+
+        pub fn remove(&mut self, key: i32) -> Option<Decimal> {
+            self.avl_tree.remove(&key)
+        }
+
+        pub fn update_value(&mut self, key: i32, value: Decimal) -> Option<Decimal> {
+            self.avl_tree
+                .update(&key, |old_value| std::mem::replace(old_value, value))
+        }
+
+        pub fn check_health(&mut self) {
+            check_aggregates(&mut self.avl_tree);
+        }
+
+        pub fn get(&mut self, key: i32) -> Option<Decimal> {
+            self.avl_tree.get(&key).map(|value| *value)
+        }
+
+        pub fn get_range(&mut self, key1: i32, key2: i32) -> Vec<(i32, Decimal)> {
+            self.avl_tree
+                .range(key1..key2)
+                .map(|(key, value, _)| (key, value))
+                .collect()
+        }
+
+        pub fn aggregate(&mut self, key1: i32, key2: i32) -> (Decimal, u64, Option<Decimal>) {
+            to_tuple(self.avl_tree.aggregate(key1..key2))
+        }
+
+        pub fn aggregate_both_included(
+            &mut self,
+            key1: i32,
+            key2: i32,
+        ) -> (Decimal, u64, Option<Decimal>) {
+            to_tuple(self.avl_tree.aggregate((Included(key1), Included(key2))))
+        }
+
+        pub fn aggregate_both_excluded(
+            &mut self,
+            key1: i32,
+            key2: i32,
+        ) -> (Decimal, u64, Option<Decimal>) {
+            to_tuple(self.avl_tree.aggregate((Excluded(key1), Excluded(key2))))
+        }
+
+        pub fn insert_level(&mut self, price: i32, quantity: Decimal) {
+            self.order_book.insert(price, quantity);
+        }
+
+        pub fn remove_level(&mut self, price: i32) {
+            self.order_book.remove(&price);
+        }
+
+        pub fn find_by_prefix(
+            &mut self,
+            threshold: Decimal,
+            from_back: bool,
+        ) -> Option<(i32, Decimal, Decimal)> {
+            let direction = match from_back {
+                true => Direction::Left,
+                false => Direction::Right,
+            };
+            self.order_book
+                .find_by_prefix(Quantity(threshold), direction)
+                .map(|search| (*search.key(), search.before().0, search.remaining().0))
+        }
+
+        pub fn total(&mut self) -> (Decimal, u64, Option<Decimal>) {
+            to_tuple(self.avl_tree.total())
+        }
+    }
+}
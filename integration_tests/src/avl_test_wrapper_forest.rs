@@ -0,0 +1,102 @@
+use scrypto::prelude::*;
+
+use scrypto_avltree::avl_tree_error::AvlTreeError;
+use scrypto_avltree::avl_tree_health::check_forest_health;
+use scrypto_avltree::tree_forest::{TreeForest, TreeHandle, TreeId};
+
+fn existing_tree(forest: &mut TreeForest<i32, i32>, tree: TreeId) -> TreeHandle<'_, i32, i32> {
+    forest.tree(tree).expect("Tree not found")
+}
+
+#[blueprint]
+mod avl_test_wrapper_forest {
+    struct AvlTestWrapperForest {
+        forest: TreeForest<i32, i32>,
+    }
+
+    impl AvlTestWrapperForest {
+        pub fn instantiate() -> Global<AvlTestWrapperForest> {
+            let forest = TreeForest::new();
+            let component = (Self { forest })
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+            component
+        }
+
+        pub fn create_tree(&mut self) -> TreeId {
+            self.forest.create_tree()
+        }
+
+        pub fn insert(&mut self, tree: TreeId, key: i32, value: i32) -> Option<i32> {
+            existing_tree(&mut self.forest, tree).insert(key, value)
+        }
+
+        pub fn remove(&mut self, tree: TreeId, key: i32) -> Option<i32> {
+            existing_tree(&mut self.forest, tree).remove(&key)
+        }
+
+        pub fn get(&mut self, tree: TreeId, key: i32) -> Option<i32> {
+            existing_tree(&mut self.forest, tree)
+                .get(&key)
+                .map(|value| *value)
+        }
+
+        pub fn update_value(&mut self, tree: TreeId, key: i32, value: i32) -> Option<i32> {
+            existing_tree(&mut self.forest, tree)
+                .get_mut(&key)
+                .map(|mut old_value| mem::replace(&mut *old_value, value))
+        }
+
+        pub fn move_entry(
+            &mut self,
+            from: TreeId,
+            to: TreeId,
+            key: i32,
+        ) -> Result<(), AvlTreeError<i32>> {
+            self.forest.move_entry(from, to, &key)
+        }
+
+        pub fn move_range(&mut self, from: TreeId, to: TreeId, key1: i32, key2: i32) {
+            self.forest.move_range(from, to, key1..key2);
+        }
+
+        pub fn get_range(&mut self, tree: TreeId, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            existing_tree(&mut self.forest, tree)
+                .range(key1..key2)
+                .map(|(key, value, _)| (key, value))
+                .collect()
+        }
+
+        pub fn get_all(&mut self, tree: TreeId) -> Vec<(i32, i32)> {
+            let mut handle = existing_tree(&mut self.forest, tree);
+            let all: Vec<(i32, i32)> = handle
+                .range(..)
+                .map(|(key, value, _)| (key, value))
+                .collect();
+            let mut all_back: Vec<(i32, i32)> = handle
+                .range_back(..)
+                .map(|(key, value, _)| (key, value))
+                .collect();
+            all_back.reverse();
+            assert_eq!(
+                all, all_back,
+                "Back iteration differs from forward iteration"
+            );
+            all
+        }
+
+        pub fn first_last(&mut self, tree: TreeId) -> (Option<i32>, Option<i32>) {
+            let handle = existing_tree(&mut self.forest, tree);
+            (handle.first(), handle.last())
+        }
+
+        pub fn has_tree(&mut self, tree: TreeId) -> bool {
+            self.forest.tree(tree).is_some()
+        }
+
+        pub fn check_health(&mut self, tree: TreeId) {
+            check_forest_health(&mut self.forest, tree);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use scrypto::prelude::*;
+use std::ops::Bound::Included;
+
+use scrypto_avltree::avl_tree_health::check_interval_health;
+use scrypto_avltree::interval_tree::IntervalTree;
+
+#[blueprint]
+mod avl_test_wrapper_interval {
+    struct AvlTestWrapperInterval {
+        interval_tree: IntervalTree<i32, u32>,
+    }
+
+    impl AvlTestWrapperInterval {
+        pub fn instantiate() -> Global<AvlTestWrapperInterval> {
+            let interval_tree = IntervalTree::new();
+            let component = (Self { interval_tree })
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+            component
+        }
+
+        pub fn insert(&mut self, lo: i32, hi: i32, id: u32) -> Option<u32> {
+            self.interval_tree.insert(lo, hi, id)
+        }
+
+        pub fn remove(&mut self, lo: i32, hi: i32) -> Option<u32> {
+            self.interval_tree.remove(&lo, &hi)
+        }
+
+        pub fn get(&mut self, lo: i32, hi: i32) -> Option<u32> {
+            self.interval_tree.get(&lo, &hi).map(|id| *id)
+        }
+
+        pub fn stab(&mut self, point: i32) -> Vec<(i32, i32, u32)> {
+            self.interval_tree
+                .stab(&point)
+                .map(|(interval, id)| (*interval.lo(), *interval.hi(), id))
+                .collect()
+        }
+
+        pub fn overlapping(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32, u32)> {
+            self.interval_tree
+                .overlapping(key1..key2)
+                .map(|(interval, id)| (*interval.lo(), *interval.hi(), id))
+                .collect()
+        }
+
+        pub fn overlapping_both_included(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32, u32)> {
+            self.interval_tree
+                .overlapping((Included(key1), Included(key2)))
+                .map(|(interval, id)| (*interval.lo(), *interval.hi(), id))
+                .collect()
+        }
+
+        pub fn check_health(&mut self) {
+            check_interval_health(&mut self.interval_tree);
+        }
+    }
+}
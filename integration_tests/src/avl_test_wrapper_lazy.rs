@@ -0,0 +1,95 @@
+use scrypto::prelude::*;
+use std::ops::Bound::Included;
+
+use scrypto_avltree::avl_tree_health::check_lazy_health;
+use scrypto_avltree::lazy_avl_tree::{LazyAvlTree, LazyUpdate};
+
+/// Update `value * mul + add`, which does not commute with other updates.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq)]
+pub struct Affine {
+    mul: Decimal,
+    add: Decimal,
+}
+
+impl LazyUpdate<Decimal> for Affine {
+    fn apply(&self, value: &mut Decimal) {
+        *value = *value * self.mul + self.add;
+    }
+
+    fn compose(&self, newer: &Self) -> Self {
+        Affine {
+            mul: self.mul * newer.mul,
+            add: self.add * newer.mul + newer.add,
+        }
+    }
+}
+
+#[blueprint]
+mod avl_test_wrapper_lazy {
+    struct AvlTestWrapperLazy {
+        avl_tree: LazyAvlTree<i32, Decimal, Affine>,
+    }
+
+    impl AvlTestWrapperLazy {
+        pub fn instantiate() -> Global<AvlTestWrapperLazy> {
+            let avl_tree = LazyAvlTree::new();
+            let component = (Self { avl_tree })
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+            component
+        }
+
+        pub fn insert(&mut self, key: i32, value: Decimal) -> Option<Decimal> {
+            self.avl_tree.insert(key, value)
+        }
+
+        pub fn remove(&mut self, key: i32) -> Option<Decimal> {
+            self.avl_tree.remove(&key)
+        }
+
+        pub fn get(&mut self, key: i32) -> Option<Decimal> {
+            self.avl_tree.get(&key)
+        }
+
+        pub fn update_value(&mut self, key: i32, value: Decimal) -> Option<Decimal> {
+            let mut item = self.avl_tree.get_mut(&key)?;
+            let old_value = *item;
+            *item = value;
+            Some(old_value)
+        }
+
+        pub fn range_apply(&mut self, key1: i32, key2: i32, mul: Decimal, add: Decimal) {
+            self.avl_tree.range_apply(key1..key2, Affine { mul, add });
+        }
+
+        pub fn range_apply_both_included(
+            &mut self,
+            key1: i32,
+            key2: i32,
+            mul: Decimal,
+            add: Decimal,
+        ) {
+            self.avl_tree
+                .range_apply((Included(key1), Included(key2)), Affine { mul, add });
+        }
+
+        pub fn get_range(&mut self, key1: i32, key2: i32) -> Vec<(i32, Decimal)> {
+            self.avl_tree
+                .range(key1..key2)
+                .map(|(key, value, _)| (key, value))
+                .collect()
+        }
+
+        pub fn get_range_back(&mut self, key1: i32, key2: i32) -> Vec<(i32, Decimal)> {
+            self.avl_tree
+                .range_back(key1..key2)
+                .map(|(key, value, _)| (key, value))
+                .collect()
+        }
+
+        pub fn check_health(&mut self) {
+            check_lazy_health(&mut self.avl_tree);
+        }
+    }
+}
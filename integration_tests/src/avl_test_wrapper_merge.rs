@@ -0,0 +1,63 @@
+use scrypto::prelude::*;
+
+use scrypto_avltree::avl_tree::AvlTree;
+use scrypto_avltree::merge_iter::MergeIter;
+
+#[blueprint]
+mod avl_test_wrapper_merge {
+    struct AvlTestWrapperMerge {
+        avl_trees: Vec<AvlTree<i32, i32>>,
+    }
+
+    impl AvlTestWrapperMerge {
+        pub fn instantiate(tree_count: u32) -> Global<AvlTestWrapperMerge> {
+            let avl_trees = (0..tree_count).map(|_| AvlTree::new()).collect();
+            let component = (Self { avl_trees })
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+            component
+        }
+
+        pub fn insert(&mut self, source: u32, key: i32, value: i32) -> Option<i32> {
+            self.avl_trees[source as usize].insert(key, value)
+        }
+
+        pub fn get_merged(&mut self, key1: i32, key2: i32) -> Vec<(u32, i32, i32)> {
+            let trees: Vec<&AvlTree<i32, i32>> = self.avl_trees.iter().collect();
+            MergeIter::range(&trees, key1..key2)
+                .map(|(source, key, value)| (source as u32, key, value))
+                .collect()
+        }
+
+        pub fn get_merged_back(&mut self, key1: i32, key2: i32) -> Vec<(u32, i32, i32)> {
+            let trees: Vec<&AvlTree<i32, i32>> = self.avl_trees.iter().collect();
+            MergeIter::range_back(&trees, key1..key2)
+                .map(|(source, key, value)| (source as u32, key, value))
+                .collect()
+        }
+
+        /// Merges a different range of every tree, the range of tree i starts at `starts[i]`.
+        pub fn get_merged_from(&mut self, starts: Vec<i32>) -> Vec<(u32, i32, i32)> {
+            let iterators = self
+                .avl_trees
+                .iter()
+                .zip(starts)
+                .map(|(tree, start)| tree.range(start..))
+                .collect();
+            MergeIter::new(iterators)
+                .map(|(source, key, value)| (source as u32, key, value))
+                .collect()
+        }
+
+        pub fn get_merged_mixed_directions(&mut self) -> Vec<(u32, i32, i32)> {
+            let iterators = vec![
+                self.avl_trees[0].range(..),
+                self.avl_trees[1].range_back(..),
+            ];
+            MergeIter::new(iterators)
+                .map(|(source, key, value)| (source as u32, key, value))
+                .collect()
+        }
+    }
+}
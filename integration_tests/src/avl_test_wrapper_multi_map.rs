@@ -0,0 +1,67 @@
+use scrypto::prelude::*;
+
+use scrypto_avltree::avl_multi_map::AvlMultiMap;
+use scrypto_avltree::avl_tree_health::check_multi_map_health;
+
+#[blueprint]
+mod avl_test_wrapper_multi_map {
+    struct AvlTestWrapperMultiMap {
+        avl_map: AvlMultiMap<i32, i32>,
+    }
+
+    impl AvlTestWrapperMultiMap {
+        pub fn instantiate() -> Global<AvlTestWrapperMultiMap> {
+            let avl_map = AvlMultiMap::new();
+            let component = (Self { avl_map })
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+            component
+        }
+
+        pub fn insert(&mut self, key: i32, value: i32) {
+            self.avl_map.insert(key, value);
+        }
+
+        pub fn remove_one(&mut self, key: i32) -> Option<i32> {
+            self.avl_map.remove_one(&key)
+        }
+
+        pub fn remove_all(&mut self, key: i32) -> Vec<i32> {
+            self.avl_map.remove_all(&key)
+        }
+
+        pub fn get_all(&mut self, key: i32) -> Vec<i32> {
+            self.avl_map.get_all(&key).collect()
+        }
+
+        pub fn contains_key(&mut self, key: i32) -> bool {
+            self.avl_map.contains_key(&key)
+        }
+
+        pub fn get_range(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            self.avl_map
+                .range(key1..key2)
+                .map(|(key, value, _)| (key, value))
+                .collect()
+        }
+
+        pub fn get_range_back(&mut self, key1: i32, key2: i32) -> Vec<(i32, i32)> {
+            self.avl_map
+                .range_back(key1..key2)
+                .map(|(key, value, _)| (key, value))
+                .collect()
+        }
+
+        pub fn get_range_next_keys(&mut self, key1: i32, key2: i32) -> Vec<Option<i32>> {
+            self.avl_map
+                .range(key1..key2)
+                .map(|(_, _, next_key)| next_key)
+                .collect()
+        }
+
+        pub fn check_health(&mut self) {
+            check_multi_map_health(&mut self.avl_map);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use scrypto::prelude::*;
+
+use scrypto_avltree::avl_tree_health::check_sequence_health;
+use scrypto_avltree::sequence_tree::SequenceTree;
+
+#[blueprint]
+mod avl_test_wrapper_sequence {
+    struct AvlTestWrapperSequence {
+        sequence: SequenceTree<i32>,
+    }
+
+    impl AvlTestWrapperSequence {
+        pub fn instantiate() -> Global<AvlTestWrapperSequence> {
+            let sequence = SequenceTree::new();
+            let component = (Self { sequence })
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+            component
+        }
+
+        pub fn insert_at(&mut self, index: u64, value: i32) {
+            self.sequence.insert_at(index, value);
+        }
+
+        pub fn push_back(&mut self, value: i32) {
+            self.sequence.push_back(value);
+        }
+
+        pub fn push_front(&mut self, value: i32) {
+            self.sequence.push_front(value);
+        }
+
+        pub fn remove_at(&mut self, index: u64) -> Option<i32> {
+            self.sequence.remove_at(index)
+        }
+
+        pub fn get(&mut self, index: u64) -> Option<i32> {
+            self.sequence.get(index).map(|value| *value)
+        }
+
+        pub fn update_value(&mut self, index: u64, value: i32) -> Option<i32> {
+            self.sequence
+                .update(index, |old_value| mem::replace(old_value, value))
+        }
+
+        pub fn len(&mut self) -> u64 {
+            self.sequence.len()
+        }
+
+        pub fn get_all(&mut self) -> Vec<i32> {
+            self.sequence.iter().collect()
+        }
+
+        pub fn get_all_back(&mut self) -> Vec<i32> {
+            self.sequence.iter().rev().collect()
+        }
+
+        pub fn check_health(&mut self) {
+            check_sequence_health(&mut self.sequence);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use scrypto::prelude::*;
+
+use scrypto_avltree::avl_set::AvlSet;
+use scrypto_avltree::avl_tree_health::check_set_health;
+
+#[blueprint]
+mod avl_test_wrapper_set {
+    struct AvlTestWrapperSet {
+        avl_set: AvlSet<i32>,
+        other_set: AvlSet<i32>,
+    }
+
+    impl AvlTestWrapperSet {
+        pub fn instantiate() -> Global<AvlTestWrapperSet> {
+            let avl_set = AvlSet::new();
+            let other_set = AvlSet::new();
+            let component = (Self { avl_set, other_set })
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize();
+            component
+        }
+
+        pub fn insert(&mut self, key: i32) -> bool {
+            self.avl_set.insert(key)
+        }
+
+        pub fn insert_other(&mut self, key: i32) -> bool {
+            self.other_set.insert(key)
+        }
+
+        pub fn remove(&mut self, key: i32) -> bool {
+            self.avl_set.remove(&key)
+        }
+
+        pub fn contains(&mut self, key: i32) -> bool {
+            self.avl_set.contains(&key)
+        }
+
+        pub fn get_range(&mut self, key1: i32, key2: i32) -> Vec<i32> {
+            self.avl_set.range(key1..key2).collect()
+        }
+
+        pub fn get_range_back(&mut self, key1: i32, key2: i32) -> Vec<i32> {
+            self.avl_set.range_back(key1..key2).collect()
+        }
+
+        pub fn first_last(&mut self) -> (Option<i32>, Option<i32>) {
+            (self.avl_set.first(), self.avl_set.last())
+        }
+
+        pub fn floor_ceiling(&mut self, key: i32) -> (Option<i32>, Option<i32>) {
+            (self.avl_set.floor(&key), self.avl_set.ceiling(&key))
+        }
+
+        pub fn union(&mut self) -> Vec<i32> {
+            self.avl_set.union(&self.other_set).collect()
+        }
+
+        pub fn intersection(&mut self) -> Vec<i32> {
+            self.avl_set.intersection(&self.other_set).collect()
+        }
+
+        pub fn difference(&mut self) -> Vec<i32> {
+            self.avl_set.difference(&self.other_set).collect()
+        }
+
+        pub fn symmetric_difference(&mut self) -> Vec<i32> {
+            self.avl_set.symmetric_difference(&self.other_set).collect()
+        }
+
+        pub fn check_health(&mut self) {
+            check_set_health(&mut self.avl_set);
+        }
+    }
+}
@@ -1,2 +1,10 @@
 mod avl_test_wrapper;
+mod avl_test_wrapper_augmented;
 mod avl_test_wrapper_decimal;
+mod avl_test_wrapper_forest;
+mod avl_test_wrapper_interval;
+mod avl_test_wrapper_lazy;
+mod avl_test_wrapper_merge;
+mod avl_test_wrapper_multi_map;
+mod avl_test_wrapper_sequence;
+mod avl_test_wrapper_set;
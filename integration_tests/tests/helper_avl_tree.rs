@@ -1,5 +1,6 @@
 use radix_transactions::builder::ManifestBuilder;
 use scrypto::prelude::*;
+use scrypto_avltree::avl_tree_error::AvlTreeError;
 use scrypto_testenv::*;
 use std::mem;
 
@@ -48,6 +49,43 @@ impl TestHelper {
         receipt
     }
 
+    pub fn instantiate_with_store(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapper",
+            "instantiate_with_store",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate_with_store", 1, 0);
+        self
+    }
+
+    pub fn instantiate_with_store_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate_with_store();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate_with_store")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn store_address(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "store_address",
+            manifest_args!(),
+        );
+        self.env.new_instruction("store_address", 1, 0);
+        self
+    }
+
+    pub fn store_address_success(&mut self) -> InternalAddress {
+        let receipt = self.store_address().execute_expect_success(false);
+        let output: Vec<InternalAddress> = receipt.outputs("store_address");
+        output[0]
+    }
+
     pub fn insert(&mut self, key: i32, value: i32) -> &mut TestHelper {
         let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
         self.env.manifest_builder = manifest_builder.call_method(
@@ -75,6 +113,28 @@ impl TestHelper {
         self
     }
 
+    pub fn bulk_load(&mut self, start_key: i32, end_key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "bulk_load",
+            manifest_args!(start_key, end_key),
+        );
+        self.env.new_instruction("bulk_load", 1, 0);
+        self
+    }
+
+    pub fn bulk_delete(&mut self, limit: u32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "bulk_delete",
+            manifest_args!(limit),
+        );
+        self.env.new_instruction("bulk_delete", 1, 0);
+        self
+    }
+
     pub fn check_health(&mut self) -> &mut TestHelper {
         let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
         self.env.manifest_builder = manifest_builder.call_method(
@@ -248,6 +308,135 @@ impl TestHelper {
         self
     }
 
+    pub fn start_range_page(&mut self, key1: i32, key2: i32, back: bool) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "start_range_page",
+            manifest_args!(key1, key2, back),
+        );
+        self.env.new_instruction("start_range_page", 1, 0);
+        self
+    }
+
+    pub fn next_range_page(&mut self, limit: u32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "next_range_page",
+            manifest_args!(limit),
+        );
+        self.env.new_instruction("next_range_page", 1, 0);
+        self
+    }
+
+    pub fn next_range_page_success(
+        &mut self,
+        limit: u32,
+        keys_expected: Vec<i32>,
+        has_more_expected: bool,
+    ) {
+        let receipt = self.next_range_page(limit).execute_expect_success(false);
+        let output: Vec<(Vec<(i32, i32)>, bool)> = receipt.outputs("next_range_page");
+        assert_eq!(
+            output,
+            vec![(to_key_values(&keys_expected), has_more_expected)]
+        );
+    }
+
+    pub fn get_range_rev(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_rev",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_rev", 1, 0);
+        self
+    }
+
+    pub fn get_range_back_rev(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_back_rev",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_back_rev", 1, 0);
+        self
+    }
+
+    pub fn get_range_alternating(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        back_first: bool,
+    ) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_alternating",
+            manifest_args!(key1, key2, back_first),
+        );
+        self.env.new_instruction("get_range_alternating", 1, 0);
+        self
+    }
+
+    pub fn get_range_ref(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_ref",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_ref", 1, 0);
+        self
+    }
+
+    pub fn get_range_back_ref(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_back_ref",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_back_ref", 1, 0);
+        self
+    }
+
+    pub fn get_range_ref_held(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_ref_held",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_ref_held", 1, 0);
+        self
+    }
+
+    pub fn get_keys(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_keys",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_keys", 1, 0);
+        self
+    }
+
+    pub fn get_keys_back(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_keys_back",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_keys_back", 1, 0);
+        self
+    }
+
     pub fn get_range_success(
         &mut self,
         key1: i32,
@@ -273,6 +462,399 @@ impl TestHelper {
         let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_back");
         assert_eq!(output, vec![output_expected]);
     }
+
+    pub fn partition_point_by_key(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "partition_point_by_key",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("partition_point_by_key", 1, 0);
+        self
+    }
+
+    pub fn partition_point_by_value(&mut self, threshold: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "partition_point_by_value",
+            manifest_args!(threshold),
+        );
+        self.env.new_instruction("partition_point_by_value", 1, 0);
+        self
+    }
+
+    pub fn get_keys_success(&mut self, key1: i32, key2: i32, output_expected: Vec<i32>) {
+        let receipt = self.get_keys(key1, key2).execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_keys");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_keys_back_success(&mut self, key1: i32, key2: i32, output_expected: Vec<i32>) {
+        let receipt = self.get_keys_back(key1, key2).execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_keys_back");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn partition_point_by_key_success(&mut self, key: i32, output_expected: Option<i32>) {
+        let receipt = self
+            .partition_point_by_key(key)
+            .execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("partition_point_by_key");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn partition_point_by_value_success(
+        &mut self,
+        threshold: i32,
+        output_expected: Option<i32>,
+    ) {
+        let receipt = self
+            .partition_point_by_value(threshold)
+            .execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("partition_point_by_value");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn try_insert(&mut self, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "try_insert",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("try_insert", 1, 0);
+        self
+    }
+
+    pub fn try_insert_success(
+        &mut self,
+        key: i32,
+        value: i32,
+        output_expected: Result<Option<i32>, AvlTreeError<i32>>,
+    ) {
+        let receipt = self.try_insert(key, value).execute_expect_success(false);
+        let output: Vec<Result<Option<i32>, AvlTreeError<i32>>> = receipt.outputs("try_insert");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn try_remove(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "try_remove",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("try_remove", 1, 0);
+        self
+    }
+
+    pub fn try_remove_success(
+        &mut self,
+        key: i32,
+        output_expected: Result<Option<i32>, AvlTreeError<i32>>,
+    ) {
+        let receipt = self.try_remove(key).execute_expect_success(false);
+        let output: Vec<Result<Option<i32>, AvlTreeError<i32>>> = receipt.outputs("try_remove");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn try_update_value(&mut self, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "try_update_value",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("try_update_value", 1, 0);
+        self
+    }
+
+    pub fn try_update_value_success(
+        &mut self,
+        key: i32,
+        value: i32,
+        output_expected: Result<i32, AvlTreeError<i32>>,
+    ) {
+        let receipt = self
+            .try_update_value(key, value)
+            .execute_expect_success(false);
+        let output: Vec<Result<i32, AvlTreeError<i32>>> = receipt.outputs("try_update_value");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn try_get_range(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "try_get_range",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("try_get_range", 1, 0);
+        self
+    }
+
+    pub fn try_get_range_success(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        output_expected: Result<Vec<(i32, i32)>, AvlTreeError<i32>>,
+    ) {
+        let receipt = self.try_get_range(key1, key2).execute_expect_success(false);
+        let output: Vec<Result<Vec<(i32, i32)>, AvlTreeError<i32>>> =
+            receipt.outputs("try_get_range");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn insert_if_absent(&mut self, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert_if_absent",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("insert_if_absent", 1, 0);
+        self
+    }
+
+    pub fn insert_if_absent_success(
+        &mut self,
+        key: i32,
+        value: i32,
+        output_expected: Result<(), i32>,
+    ) {
+        let receipt = self
+            .insert_if_absent(key, value)
+            .execute_expect_success(false);
+        let output: Vec<Result<(), i32>> = receipt.outputs("insert_if_absent");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn compare_and_swap(&mut self, key: i32, expected: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "compare_and_swap",
+            manifest_args!(key, expected, value),
+        );
+        self.env.new_instruction("compare_and_swap", 1, 0);
+        self
+    }
+
+    pub fn compare_and_swap_success(
+        &mut self,
+        key: i32,
+        expected: i32,
+        value: i32,
+        output_expected: Result<i32, Option<i32>>,
+    ) {
+        let receipt = self
+            .compare_and_swap(key, expected, value)
+            .execute_expect_success(false);
+        let output: Vec<Result<i32, Option<i32>>> = receipt.outputs("compare_and_swap");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn withdraw(&mut self, key: i32, amount: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "withdraw",
+            manifest_args!(key, amount),
+        );
+        self.env.new_instruction("withdraw", 1, 0);
+        self
+    }
+
+    pub fn withdraw_success(&mut self, key: i32, amount: i32, output_expected: Option<i32>) {
+        let receipt = self.withdraw(key, amount).execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("withdraw");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn rekey(&mut self, old_key: i32, new_key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "rekey",
+            manifest_args!(old_key, new_key),
+        );
+        self.env.new_instruction("rekey", 1, 0);
+        self
+    }
+
+    pub fn rekey_success(
+        &mut self,
+        old_key: i32,
+        new_key: i32,
+        output_expected: Result<(), AvlTreeError<i32>>,
+    ) {
+        let receipt = self.rekey(old_key, new_key).execute_expect_success(false);
+        let output: Vec<Result<(), AvlTreeError<i32>>> = receipt.outputs("rekey");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn insert_with_hint(&mut self, hint: i32, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert_with_hint",
+            manifest_args!(hint, key, value),
+        );
+        self.env.new_instruction("insert_with_hint", 1, 0);
+        self
+    }
+
+    pub fn get_near(&mut self, hint: i32, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_near",
+            manifest_args!(hint, key),
+        );
+        self.env.new_instruction("get_near", 1, 0);
+        self
+    }
+
+    pub fn get_near_success(&mut self, hint: i32, key: i32, output_expected: Option<(i32, i32)>) {
+        let receipt = self.get_near(hint, key).execute_expect_success(false);
+        let output: Vec<Option<(i32, i32)>> = receipt.outputs("get_near");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn insert_sorted_batch(&mut self, keys: Vec<i32>) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert_sorted_batch",
+            manifest_args!(keys),
+        );
+        self.env.new_instruction("insert_sorted_batch", 1, 0);
+        self
+    }
+
+    pub fn remove_range(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "remove_range",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("remove_range", 1, 0);
+        self
+    }
+
+    pub fn remove_range_success(&mut self, key1: i32, key2: i32, output_expected: u64) {
+        let receipt = self.remove_range(key1, key2).execute_expect_success(false);
+        let output: Vec<u64> = receipt.outputs("remove_range");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn drain_range(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "drain_range",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("drain_range", 1, 0);
+        self
+    }
+
+    pub fn drain_range_success(&mut self, key1: i32, key2: i32, output_expected: Vec<(i32, i32)>) {
+        let receipt = self.drain_range(key1, key2).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("drain_range");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn split_off(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "split_off",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("split_off", 1, 0);
+        self
+    }
+
+    pub fn split_off_success(&mut self, key: i32, output_expected: u64) {
+        let receipt = self.split_off(key).execute_expect_success(false);
+        let output: Vec<u64> = receipt.outputs("split_off");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn append_split(&mut self, index: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "append_split",
+            manifest_args!(index),
+        );
+        self.env.new_instruction("append_split", 1, 0);
+        self
+    }
+
+    pub fn append_to_split(&mut self, index: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "append_to_split",
+            manifest_args!(index),
+        );
+        self.env.new_instruction("append_to_split", 1, 0);
+        self
+    }
+
+    pub fn check_split_health(&mut self, index: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_split_health",
+            manifest_args!(index),
+        );
+        self.env.new_instruction("check_split_health", 1, 0);
+        self
+    }
+
+    pub fn get_split_range(&mut self, index: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_split_range",
+            manifest_args!(index),
+        );
+        self.env.new_instruction("get_split_range", 1, 0);
+        self
+    }
+
+    pub fn get_split_range_success(&mut self, index: u64, output_expected: Vec<(i32, i32)>) {
+        let receipt = self.get_split_range(index).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_split_range");
+        assert_eq!(output, vec![output_expected]);
+    }
+}
+
+/// Instantiates a tree with the keys and `key * 10` as values, see `helper_with_key_values`.
+pub fn helper_with_keys(keys: &[i32]) -> TestHelper {
+    let key_values: Vec<(i32, i32)> = keys.iter().map(|key| (*key, *key * 10)).collect();
+    helper_with_key_values(&key_values)
+}
+
+/// Instantiates a tree with the key value pairs, inserted in transactions of 10 inserts
+/// that each check the health of the tree.
+pub fn helper_with_key_values(key_values: &[(i32, i32)]) -> TestHelper {
+    let mut helper = TestHelper::new();
+    helper.instantiate_default(false);
+    for chunk in key_values.chunks(10) {
+        for (key, value) in chunk.iter() {
+            helper.insert(*key, *value);
+        }
+        helper.check_health();
+        helper.execute_expect_success(false);
+    }
+    helper
 }
 
 pub fn to_key_values(vector: &Vec<i32>) -> Vec<(i32, i32)> {
@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::mem;
+use std::ops::Bound::{self, Excluded, Included};
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub type Summary = (Decimal, u64, Option<Decimal>);
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperAugmented",
+            "instantiate",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn insert(&mut self, key: i32, value: Decimal) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("insert", 1, 0);
+        self
+    }
+
+    pub fn remove(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "remove", manifest_args!(key));
+        self.env.new_instruction("remove", 1, 0);
+        self
+    }
+
+    pub fn update_value(&mut self, key: i32, value: Decimal) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "update_value",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("update_value", 1, 0);
+        self
+    }
+
+    pub fn check_health(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_health",
+            manifest_args!(),
+        );
+        self.env.new_instruction("check_health", 1, 0);
+        self
+    }
+
+    pub fn aggregate(&mut self, method: &str, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            method,
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction(method, 1, 0);
+        self
+    }
+
+    pub fn insert_level(&mut self, price: i32, quantity: Decimal) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert_level",
+            manifest_args!(price, quantity),
+        );
+        self.env.new_instruction("insert_level", 1, 0);
+        self
+    }
+
+    pub fn remove_level(&mut self, price: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "remove_level",
+            manifest_args!(price),
+        );
+        self.env.new_instruction("remove_level", 1, 0);
+        self
+    }
+
+    pub fn find_by_prefix(&mut self, threshold: Decimal, from_back: bool) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "find_by_prefix",
+            manifest_args!(threshold, from_back),
+        );
+        self.env.new_instruction("find_by_prefix", 1, 0);
+        self
+    }
+
+    pub fn find_by_prefix_success(
+        &mut self,
+        threshold: Decimal,
+        from_back: bool,
+        output_expected: Option<(i32, Decimal, Decimal)>,
+    ) {
+        let receipt = self
+            .find_by_prefix(threshold, from_back)
+            .execute_expect_success(false);
+        let output: Vec<Option<(i32, Decimal, Decimal)>> = receipt.outputs("find_by_prefix");
+        assert_eq!(
+            output,
+            vec![output_expected],
+            "find_by_prefix({}, {})",
+            threshold,
+            from_back
+        );
+    }
+
+    pub fn total(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "total", manifest_args!());
+        self.env.new_instruction("total", 1, 0);
+        self
+    }
+
+    pub fn aggregate_success(
+        &mut self,
+        method: &str,
+        key1: i32,
+        key2: i32,
+        output_expected: Summary,
+    ) {
+        let receipt = self
+            .aggregate(method, key1, key2)
+            .execute_expect_success(false);
+        let output: Vec<Summary> = receipt.outputs(method);
+        assert_eq!(
+            output,
+            vec![output_expected],
+            "{}({}, {})",
+            method,
+            key1,
+            key2
+        );
+    }
+
+    pub fn total_success(&mut self, output_expected: Summary) {
+        let receipt = self.total().execute_expect_success(false);
+        let output: Vec<Summary> = receipt.outputs("total");
+        assert_eq!(output, vec![output_expected]);
+    }
+}
+
+/// Computes the expected aggregate of a range from a plain map.
+pub fn expected_summary(
+    entries: &BTreeMap<i32, Decimal>,
+    start: Bound<i32>,
+    end: Bound<i32>,
+) -> Summary {
+    if let (Included(s) | Excluded(s), Included(e) | Excluded(e)) = (start, end) {
+        if s > e {
+            return (Decimal::ZERO, 0, None);
+        }
+    }
+    if let (Excluded(s), Excluded(e)) = (start, end) {
+        if s == e {
+            return (Decimal::ZERO, 0, None);
+        }
+    }
+    entries
+        .range((start, end))
+        .fold((Decimal::ZERO, 0, None), |(sum, count, max), (_, v)| {
+            (sum + *v, count + 1, max.max(Some(*v)))
+        })
+}
+
+/// Checks the aggregates of the tree with a set of ranges against the plain map.
+pub fn assert_aggregates(helper: &mut TestHelper, entries: &BTreeMap<i32, Decimal>) {
+    helper.check_health();
+    helper.execute_expect_success(false);
+    let full = expected_summary(entries, Bound::Unbounded, Bound::Unbounded);
+    helper.total_success(full);
+    let bounds: Vec<(i32, i32)> = vec![(-5, 200), (0, 10), (3, 17), (10, 11), (25, 60), (7, 7)];
+    for (key1, key2) in bounds {
+        helper.aggregate_success(
+            "aggregate",
+            key1,
+            key2,
+            expected_summary(entries, Included(key1), Excluded(key2)),
+        );
+        helper.aggregate_success(
+            "aggregate_both_included",
+            key1,
+            key2,
+            expected_summary(entries, Included(key1), Included(key2)),
+        );
+        helper.aggregate_success(
+            "aggregate_both_excluded",
+            key1,
+            key2,
+            expected_summary(entries, Excluded(key1), Excluded(key2)),
+        );
+    }
+}
+
+/// Computes the expected result of find_by_prefix from a plain map.
+pub fn expected_prefix_search(
+    levels: &BTreeMap<i32, Decimal>,
+    threshold: Decimal,
+    from_back: bool,
+) -> Option<(i32, Decimal, Decimal)> {
+    let mut before = Decimal::ZERO;
+    let ordered: Vec<(&i32, &Decimal)> = match from_back {
+        true => levels.iter().rev().collect(),
+        false => levels.iter().collect(),
+    };
+    for (price, quantity) in ordered {
+        if before + *quantity >= threshold {
+            return Some((*price, before, threshold - before));
+        }
+        before += *quantity;
+    }
+    None
+}
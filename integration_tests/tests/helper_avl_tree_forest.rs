@@ -0,0 +1,287 @@
+use std::mem;
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_avltree::avl_tree_error::AvlTreeError;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperForest",
+            "instantiate",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn create_tree(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "create_tree",
+            manifest_args!(),
+        );
+        self.env.new_instruction("create_tree", 1, 0);
+        self
+    }
+
+    pub fn insert(&mut self, tree: u64, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert",
+            manifest_args!(tree, key, value),
+        );
+        self.env.new_instruction("insert", 1, 0);
+        self
+    }
+
+    pub fn remove(&mut self, tree: u64, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "remove",
+            manifest_args!(tree, key),
+        );
+        self.env.new_instruction("remove", 1, 0);
+        self
+    }
+
+    pub fn get(&mut self, tree: u64, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get",
+            manifest_args!(tree, key),
+        );
+        self.env.new_instruction("get", 1, 0);
+        self
+    }
+
+    pub fn update_value(&mut self, tree: u64, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "update_value",
+            manifest_args!(tree, key, value),
+        );
+        self.env.new_instruction("update_value", 1, 0);
+        self
+    }
+
+    pub fn move_entry(&mut self, from: u64, to: u64, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "move_entry",
+            manifest_args!(from, to, key),
+        );
+        self.env.new_instruction("move_entry", 1, 0);
+        self
+    }
+
+    pub fn move_range(&mut self, from: u64, to: u64, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "move_range",
+            manifest_args!(from, to, key1, key2),
+        );
+        self.env.new_instruction("move_range", 1, 0);
+        self
+    }
+
+    pub fn get_range(&mut self, tree: u64, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range",
+            manifest_args!(tree, key1, key2),
+        );
+        self.env.new_instruction("get_range", 1, 0);
+        self
+    }
+
+    pub fn get_all(&mut self, tree: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_all",
+            manifest_args!(tree),
+        );
+        self.env.new_instruction("get_all", 1, 0);
+        self
+    }
+
+    pub fn first_last(&mut self, tree: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "first_last",
+            manifest_args!(tree),
+        );
+        self.env.new_instruction("first_last", 1, 0);
+        self
+    }
+
+    pub fn has_tree(&mut self, tree: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "has_tree",
+            manifest_args!(tree),
+        );
+        self.env.new_instruction("has_tree", 1, 0);
+        self
+    }
+
+    pub fn check_health(&mut self, tree: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_health",
+            manifest_args!(tree),
+        );
+        self.env.new_instruction("check_health", 1, 0);
+        self
+    }
+
+    pub fn create_tree_success(&mut self, output_expected: u64) {
+        let receipt = self.create_tree().execute_expect_success(false);
+        let output: Vec<u64> = receipt.outputs("create_tree");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn insert_success(
+        &mut self,
+        tree: u64,
+        key: i32,
+        value: i32,
+        output_expected: Option<i32>,
+    ) {
+        let receipt = self.insert(tree, key, value).execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("insert");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn remove_success(&mut self, tree: u64, key: i32, output_expected: Option<i32>) {
+        let receipt = self.remove(tree, key).execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("remove");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_success(&mut self, tree: u64, key: i32, output_expected: Option<i32>) {
+        let receipt = self.get(tree, key).execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("get");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn update_value_success(
+        &mut self,
+        tree: u64,
+        key: i32,
+        value: i32,
+        output_expected: Option<i32>,
+    ) {
+        let receipt = self
+            .update_value(tree, key, value)
+            .execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("update_value");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn move_entry_success(
+        &mut self,
+        from: u64,
+        to: u64,
+        key: i32,
+        output_expected: Result<(), AvlTreeError<i32>>,
+    ) {
+        let receipt = self.move_entry(from, to, key).execute_expect_success(false);
+        let output: Vec<Result<(), AvlTreeError<i32>>> = receipt.outputs("move_entry");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn move_range_success(&mut self, from: u64, to: u64, key1: i32, key2: i32) {
+        self.move_range(from, to, key1, key2)
+            .execute_expect_success(false);
+    }
+
+    pub fn get_range_success(
+        &mut self,
+        tree: u64,
+        key1: i32,
+        key2: i32,
+        output_expected: Vec<(i32, i32)>,
+    ) {
+        let receipt = self
+            .get_range(tree, key1, key2)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_all_success(&mut self, tree: u64, output_expected: Vec<(i32, i32)>) {
+        let receipt = self.get_all(tree).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_all");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn first_last_success(&mut self, tree: u64, output_expected: (Option<i32>, Option<i32>)) {
+        let receipt = self.first_last(tree).execute_expect_success(false);
+        let output: Vec<(Option<i32>, Option<i32>)> = receipt.outputs("first_last");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn has_tree_success(&mut self, tree: u64, output_expected: bool) {
+        let receipt = self.has_tree(tree).execute_expect_success(false);
+        let output: Vec<bool> = receipt.outputs("has_tree");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    /// Checks the health of the tree and compares all entries and the outermost keys with the expected entries.
+    pub fn assert_tree(&mut self, tree: u64, expected: &Vec<(i32, i32)>) {
+        self.check_health(tree);
+        self.execute_expect_success(false);
+        self.get_all_success(tree, expected.clone());
+        self.first_last_success(
+            tree,
+            (
+                expected.first().map(|(key, _)| *key),
+                expected.last().map(|(key, _)| *key),
+            ),
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::mem;
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperInterval",
+            "instantiate",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn insert(&mut self, lo: i32, hi: i32, id: u32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert",
+            manifest_args!(lo, hi, id),
+        );
+        self.env.new_instruction("insert", 1, 0);
+        self
+    }
+
+    pub fn remove(&mut self, lo: i32, hi: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "remove",
+            manifest_args!(lo, hi),
+        );
+        self.env.new_instruction("remove", 1, 0);
+        self
+    }
+
+    pub fn get(&mut self, lo: i32, hi: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "get", manifest_args!(lo, hi));
+        self.env.new_instruction("get", 1, 0);
+        self
+    }
+
+    pub fn stab(&mut self, point: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "stab", manifest_args!(point));
+        self.env.new_instruction("stab", 1, 0);
+        self
+    }
+
+    pub fn overlapping(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "overlapping",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("overlapping", 1, 0);
+        self
+    }
+
+    pub fn overlapping_both_included(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "overlapping_both_included",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("overlapping_both_included", 1, 0);
+        self
+    }
+
+    pub fn check_health(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_health",
+            manifest_args!(),
+        );
+        self.env.new_instruction("check_health", 1, 0);
+        self
+    }
+
+    pub fn get_success(&mut self, lo: i32, hi: i32, output_expected: Option<u32>) {
+        let receipt = self.get(lo, hi).execute_expect_success(false);
+        let output: Vec<Option<u32>> = receipt.outputs("get");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn stab_success(&mut self, point: i32, output_expected: Vec<(i32, i32, u32)>) {
+        let receipt = self.stab(point).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32, u32)>> = receipt.outputs("stab");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn overlapping_success(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        output_expected: Vec<(i32, i32, u32)>,
+    ) {
+        let receipt = self.overlapping(key1, key2).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32, u32)>> = receipt.outputs("overlapping");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn overlapping_both_included_success(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        output_expected: Vec<(i32, i32, u32)>,
+    ) {
+        let receipt = self
+            .overlapping_both_included(key1, key2)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32, u32)>> = receipt.outputs("overlapping_both_included");
+        assert_eq!(output, vec![output_expected]);
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::mem;
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperLazy",
+            "instantiate",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn insert(&mut self, key: i32, value: Decimal) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("insert", 1, 0);
+        self
+    }
+
+    pub fn remove(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "remove", manifest_args!(key));
+        self.env.new_instruction("remove", 1, 0);
+        self
+    }
+
+    pub fn get(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "get", manifest_args!(key));
+        self.env.new_instruction("get", 1, 0);
+        self
+    }
+
+    pub fn update_value(&mut self, key: i32, value: Decimal) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "update_value",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("update_value", 1, 0);
+        self
+    }
+
+    pub fn range_apply(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        mul: Decimal,
+        add: Decimal,
+    ) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "range_apply",
+            manifest_args!(key1, key2, mul, add),
+        );
+        self.env.new_instruction("range_apply", 1, 0);
+        self
+    }
+
+    pub fn range_apply_both_included(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        mul: Decimal,
+        add: Decimal,
+    ) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "range_apply_both_included",
+            manifest_args!(key1, key2, mul, add),
+        );
+        self.env.new_instruction("range_apply_both_included", 1, 0);
+        self
+    }
+
+    pub fn get_range(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range", 1, 0);
+        self
+    }
+
+    pub fn get_range_back(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_back",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_back", 1, 0);
+        self
+    }
+
+    pub fn check_health(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_health",
+            manifest_args!(),
+        );
+        self.env.new_instruction("check_health", 1, 0);
+        self
+    }
+}
+
+/// Keeps a plain map next to the tree and applies every operation to both.
+pub struct LazyModel {
+    pub helper: TestHelper,
+    pub entries: BTreeMap<i32, Decimal>,
+}
+
+impl LazyModel {
+    pub fn new() -> LazyModel {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        LazyModel {
+            helper,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, key: i32, value: Decimal) {
+        self.helper.insert(key, value);
+        self.entries.insert(key, value);
+    }
+
+    pub fn remove(&mut self, key: i32) {
+        self.helper.remove(key);
+        self.entries.remove(&key);
+    }
+
+    pub fn update_value(&mut self, key: i32, value: Decimal) {
+        self.helper.update_value(key, value);
+        if let Some(old_value) = self.entries.get_mut(&key) {
+            *old_value = value;
+        }
+    }
+
+    pub fn range_apply(&mut self, key1: i32, key2: i32, mul: Decimal, add: Decimal) {
+        self.helper.range_apply(key1, key2, mul, add);
+        for (_, value) in self.entries.range_mut(key1..key2.max(key1)) {
+            *value = *value * mul + add;
+        }
+    }
+
+    pub fn range_apply_both_included(&mut self, key1: i32, key2: i32, mul: Decimal, add: Decimal) {
+        self.helper.range_apply_both_included(key1, key2, mul, add);
+        for (_, value) in self.entries.range_mut(key1..=key2.max(key1)) {
+            *value = *value * mul + add;
+        }
+    }
+
+    /// Executes the collected operations and compares the tree with the map.
+    pub fn execute_and_compare(&mut self) {
+        self.helper.check_health();
+        self.helper.execute_expect_success(false);
+
+        let expected: Vec<(i32, Decimal)> = self.entries.clone().into_iter().collect();
+        let receipt = self
+            .helper
+            .get_range(i32::MIN, i32::MAX)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, Decimal)>> = receipt.outputs("get_range");
+        assert_eq!(output, vec![expected.clone()]);
+
+        let receipt = self
+            .helper
+            .get_range_back(i32::MIN, i32::MAX)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, Decimal)>> = receipt.outputs("get_range_back");
+        let reversed: Vec<(i32, Decimal)> = expected.iter().rev().cloned().collect();
+        assert_eq!(output, vec![reversed]);
+
+        // A get pushes the pending updates down its path, so only a few fit into one transaction.
+        let keys: Vec<i32> = self.entries.keys().step_by(3).cloned().collect();
+        for chunk in keys.chunks(8) {
+            for key in chunk.iter() {
+                self.helper.get(*key);
+            }
+            let receipt = self.helper.execute_expect_success(false);
+            let output: Vec<Option<Decimal>> = receipt.outputs("get");
+            let expected: Vec<Option<Decimal>> = chunk
+                .iter()
+                .map(|key| self.entries.get(key).cloned())
+                .collect();
+            assert_eq!(output, expected);
+        }
+    }
+}
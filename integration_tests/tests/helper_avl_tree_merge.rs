@@ -0,0 +1,137 @@
+use std::mem;
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self, tree_count: u32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperMerge",
+            "instantiate",
+            manifest_args!(tree_count),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, tree_count: u32, verbose: bool) -> Receipt {
+        self.instantiate(tree_count);
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn insert(&mut self, source: u32, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert",
+            manifest_args!(source, key, value),
+        );
+        self.env.new_instruction("insert", 1, 0);
+        self
+    }
+
+    pub fn get_merged(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_merged",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_merged", 1, 0);
+        self
+    }
+
+    pub fn get_merged_back(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_merged_back",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_merged_back", 1, 0);
+        self
+    }
+
+    pub fn get_merged_from(&mut self, starts: Vec<i32>) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_merged_from",
+            manifest_args!(starts),
+        );
+        self.env.new_instruction("get_merged_from", 1, 0);
+        self
+    }
+
+    pub fn get_merged_mixed_directions(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_merged_mixed_directions",
+            manifest_args!(),
+        );
+        self.env
+            .new_instruction("get_merged_mixed_directions", 1, 0);
+        self
+    }
+
+    pub fn get_merged_success(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        output_expected: Vec<(u32, i32, i32)>,
+    ) {
+        let receipt = self.get_merged(key1, key2).execute_expect_success(false);
+        let output: Vec<Vec<(u32, i32, i32)>> = receipt.outputs("get_merged");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_merged_back_success(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        output_expected: Vec<(u32, i32, i32)>,
+    ) {
+        let receipt = self
+            .get_merged_back(key1, key2)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(u32, i32, i32)>> = receipt.outputs("get_merged_back");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_merged_from_success(
+        &mut self,
+        starts: Vec<i32>,
+        output_expected: Vec<(u32, i32, i32)>,
+    ) {
+        let receipt = self.get_merged_from(starts).execute_expect_success(false);
+        let output: Vec<Vec<(u32, i32, i32)>> = receipt.outputs("get_merged_from");
+        assert_eq!(output, vec![output_expected]);
+    }
+}
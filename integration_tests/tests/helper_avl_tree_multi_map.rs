@@ -0,0 +1,202 @@
+use std::mem;
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperMultiMap",
+            "instantiate",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn insert(&mut self, key: i32, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert",
+            manifest_args!(key, value),
+        );
+        self.env.new_instruction("insert", 1, 0);
+        self
+    }
+
+    pub fn remove_one(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "remove_one",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("remove_one", 1, 0);
+        self
+    }
+
+    pub fn remove_all(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "remove_all",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("remove_all", 1, 0);
+        self
+    }
+
+    pub fn get_all(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_all",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("get_all", 1, 0);
+        self
+    }
+
+    pub fn contains_key(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "contains_key",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("contains_key", 1, 0);
+        self
+    }
+
+    pub fn get_range(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range", 1, 0);
+        self
+    }
+
+    pub fn get_range_back(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_back",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_back", 1, 0);
+        self
+    }
+
+    pub fn get_range_next_keys(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_next_keys",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_next_keys", 1, 0);
+        self
+    }
+
+    pub fn check_health(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_health",
+            manifest_args!(),
+        );
+        self.env.new_instruction("check_health", 1, 0);
+        self
+    }
+
+    pub fn remove_one_success(&mut self, key: i32, output_expected: Option<i32>) {
+        let receipt = self.remove_one(key).execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("remove_one");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn remove_all_success(&mut self, key: i32, output_expected: Vec<i32>) {
+        let receipt = self.remove_all(key).execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("remove_all");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_all_success(&mut self, key: i32, output_expected: Vec<i32>) {
+        let receipt = self.get_all(key).execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_all");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn contains_key_success(&mut self, key: i32, output_expected: bool) {
+        let receipt = self.contains_key(key).execute_expect_success(false);
+        let output: Vec<bool> = receipt.outputs("contains_key");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_range_success(&mut self, key1: i32, key2: i32, output_expected: Vec<(i32, i32)>) {
+        let receipt = self.get_range(key1, key2).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_range_back_success(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        output_expected: Vec<(i32, i32)>,
+    ) {
+        let receipt = self
+            .get_range_back(key1, key2)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_back");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_range_next_keys_success(
+        &mut self,
+        key1: i32,
+        key2: i32,
+        output_expected: Vec<Option<i32>>,
+    ) {
+        let receipt = self
+            .get_range_next_keys(key1, key2)
+            .execute_expect_success(false);
+        let output: Vec<Vec<Option<i32>>> = receipt.outputs("get_range_next_keys");
+        assert_eq!(output, vec![output_expected]);
+    }
+}
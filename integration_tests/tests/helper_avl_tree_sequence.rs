@@ -0,0 +1,195 @@
+use std::mem;
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperSequence",
+            "instantiate",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn insert_at(&mut self, index: u64, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert_at",
+            manifest_args!(index, value),
+        );
+        self.env.new_instruction("insert_at", 1, 0);
+        self
+    }
+
+    pub fn push_back(&mut self, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "push_back",
+            manifest_args!(value),
+        );
+        self.env.new_instruction("push_back", 1, 0);
+        self
+    }
+
+    pub fn push_front(&mut self, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "push_front",
+            manifest_args!(value),
+        );
+        self.env.new_instruction("push_front", 1, 0);
+        self
+    }
+
+    pub fn remove_at(&mut self, index: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "remove_at",
+            manifest_args!(index),
+        );
+        self.env.new_instruction("remove_at", 1, 0);
+        self
+    }
+
+    pub fn get(&mut self, index: u64) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "get", manifest_args!(index));
+        self.env.new_instruction("get", 1, 0);
+        self
+    }
+
+    pub fn update_value(&mut self, index: u64, value: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "update_value",
+            manifest_args!(index, value),
+        );
+        self.env.new_instruction("update_value", 1, 0);
+        self
+    }
+
+    pub fn len(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "len", manifest_args!());
+        self.env.new_instruction("len", 1, 0);
+        self
+    }
+
+    pub fn get_all(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "get_all", manifest_args!());
+        self.env.new_instruction("get_all", 1, 0);
+        self
+    }
+
+    pub fn get_all_back(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_all_back",
+            manifest_args!(),
+        );
+        self.env.new_instruction("get_all_back", 1, 0);
+        self
+    }
+
+    pub fn check_health(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_health",
+            manifest_args!(),
+        );
+        self.env.new_instruction("check_health", 1, 0);
+        self
+    }
+
+    pub fn remove_at_success(&mut self, index: u64, output_expected: Option<i32>) {
+        let receipt = self.remove_at(index).execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("remove_at");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_success(&mut self, index: u64, output_expected: Option<i32>) {
+        let receipt = self.get(index).execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("get");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn update_value_success(&mut self, index: u64, value: i32, output_expected: Option<i32>) {
+        let receipt = self
+            .update_value(index, value)
+            .execute_expect_success(false);
+        let output: Vec<Option<i32>> = receipt.outputs("update_value");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn len_success(&mut self, output_expected: u64) {
+        let receipt = self.len().execute_expect_success(false);
+        let output: Vec<u64> = receipt.outputs("len");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_all_success(&mut self, output_expected: Vec<i32>) {
+        let receipt = self.get_all().execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_all");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_all_back_success(&mut self, output_expected: Vec<i32>) {
+        let receipt = self.get_all_back().execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_all_back");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    /// Checks the health of the tree and compares all values and the length with the expected list.
+    pub fn assert_list(&mut self, expected: &Vec<i32>) {
+        self.check_health();
+        self.execute_expect_success(false);
+        self.get_all_success(expected.clone());
+        self.get_all_back_success(expected.iter().rev().cloned().collect());
+        self.len_success(expected.len() as u64);
+    }
+}
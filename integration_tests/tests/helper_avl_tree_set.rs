@@ -0,0 +1,264 @@
+use std::mem;
+
+use radix_transactions::builder::ManifestBuilder;
+use scrypto::prelude::*;
+use scrypto_testenv::*;
+
+impl TestHelperExecution for TestHelper {
+    fn env(&mut self) -> &mut TestEnvironment {
+        &mut self.env
+    }
+}
+
+pub struct TestHelper {
+    env: TestEnvironment,
+    tree_address: Option<ComponentAddress>,
+}
+
+impl TestHelper {
+    pub fn new() -> TestHelper {
+        let env = TestEnvironment::new(vec![("test", ".")].into_iter().collect());
+
+        TestHelper {
+            env,
+            tree_address: None,
+        }
+    }
+
+    pub fn instantiate(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_function(
+            self.env.package_address("test"),
+            "AvlTestWrapperSet",
+            "instantiate",
+            manifest_args!(),
+        );
+        self.env.new_instruction("instantiate", 1, 0);
+        self
+    }
+
+    pub fn instantiate_default(&mut self, verbose: bool) -> Receipt {
+        self.instantiate();
+        let receipt = self.execute_expect_success(verbose);
+        let pool_address: ComponentAddress = receipt.outputs("instantiate")[0];
+        self.tree_address = Some(pool_address);
+        receipt
+    }
+
+    pub fn insert(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "insert", manifest_args!(key));
+        self.env.new_instruction("insert", 1, 0);
+        self
+    }
+
+    pub fn insert_other(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "insert_other",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("insert_other", 1, 0);
+        self
+    }
+
+    pub fn remove(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "remove", manifest_args!(key));
+        self.env.new_instruction("remove", 1, 0);
+        self
+    }
+
+    pub fn contains(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "contains",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("contains", 1, 0);
+        self
+    }
+
+    pub fn get_range(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range", 1, 0);
+        self
+    }
+
+    pub fn get_range_back(&mut self, key1: i32, key2: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "get_range_back",
+            manifest_args!(key1, key2),
+        );
+        self.env.new_instruction("get_range_back", 1, 0);
+        self
+    }
+
+    pub fn first_last(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "first_last",
+            manifest_args!(),
+        );
+        self.env.new_instruction("first_last", 1, 0);
+        self
+    }
+
+    pub fn floor_ceiling(&mut self, key: i32) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "floor_ceiling",
+            manifest_args!(key),
+        );
+        self.env.new_instruction("floor_ceiling", 1, 0);
+        self
+    }
+
+    pub fn union(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder =
+            manifest_builder.call_method(self.tree_address.unwrap(), "union", manifest_args!());
+        self.env.new_instruction("union", 1, 0);
+        self
+    }
+
+    pub fn intersection(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "intersection",
+            manifest_args!(),
+        );
+        self.env.new_instruction("intersection", 1, 0);
+        self
+    }
+
+    pub fn difference(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "difference",
+            manifest_args!(),
+        );
+        self.env.new_instruction("difference", 1, 0);
+        self
+    }
+
+    pub fn symmetric_difference(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "symmetric_difference",
+            manifest_args!(),
+        );
+        self.env.new_instruction("symmetric_difference", 1, 0);
+        self
+    }
+
+    pub fn check_health(&mut self) -> &mut TestHelper {
+        let manifest_builder = mem::replace(&mut self.env.manifest_builder, ManifestBuilder::new());
+        self.env.manifest_builder = manifest_builder.call_method(
+            self.tree_address.unwrap(),
+            "check_health",
+            manifest_args!(),
+        );
+        self.env.new_instruction("check_health", 1, 0);
+        self
+    }
+
+    pub fn insert_success(&mut self, key: i32, output_expected: bool) {
+        let receipt = self.insert(key).execute_expect_success(false);
+        let output: Vec<bool> = receipt.outputs("insert");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn remove_success(&mut self, key: i32, output_expected: bool) {
+        let receipt = self.remove(key).execute_expect_success(false);
+        let output: Vec<bool> = receipt.outputs("remove");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn contains_success(&mut self, key: i32, output_expected: bool) {
+        let receipt = self.contains(key).execute_expect_success(false);
+        let output: Vec<bool> = receipt.outputs("contains");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_range_success(&mut self, key1: i32, key2: i32, output_expected: Vec<i32>) {
+        let receipt = self.get_range(key1, key2).execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_range");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn get_range_back_success(&mut self, key1: i32, key2: i32, output_expected: Vec<i32>) {
+        let receipt = self
+            .get_range_back(key1, key2)
+            .execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_range_back");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn first_last_success(&mut self, output_expected: (Option<i32>, Option<i32>)) {
+        let receipt = self.first_last().execute_expect_success(false);
+        let output: Vec<(Option<i32>, Option<i32>)> = receipt.outputs("first_last");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn floor_ceiling_success(&mut self, key: i32, output_expected: (Option<i32>, Option<i32>)) {
+        let receipt = self.floor_ceiling(key).execute_expect_success(false);
+        let output: Vec<(Option<i32>, Option<i32>)> = receipt.outputs("floor_ceiling");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn union_success(&mut self, output_expected: Vec<i32>) {
+        let receipt = self.union().execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("union");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn intersection_success(&mut self, output_expected: Vec<i32>) {
+        let receipt = self.intersection().execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("intersection");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn difference_success(&mut self, output_expected: Vec<i32>) {
+        let receipt = self.difference().execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("difference");
+        assert_eq!(output, vec![output_expected]);
+    }
+
+    pub fn symmetric_difference_success(&mut self, output_expected: Vec<i32>) {
+        let receipt = self.symmetric_difference().execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("symmetric_difference");
+        assert_eq!(output, vec![output_expected]);
+    }
+}
+
+/// Instantiates a set with the keys, inserted in transactions of 10 inserts
+/// that each check the health of the set.
+pub fn helper_with_keys(keys: &[i32]) -> TestHelper {
+    let mut helper = TestHelper::new();
+    helper.instantiate_default(false);
+    for chunk in keys.chunks(10) {
+        for key in chunk.iter() {
+            helper.insert(*key);
+        }
+        helper.check_health();
+        helper.execute_expect_success(false);
+    }
+    helper
+}
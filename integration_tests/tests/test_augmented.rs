@@ -0,0 +1,113 @@
+mod helper_avl_tree_augmented;
+
+#[cfg(test)]
+mod avltree_augmented {
+    use super::*;
+    use helper_avl_tree_augmented::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+    use std::collections::BTreeMap;
+
+    fn value_of(key: i32) -> Decimal {
+        Decimal::from((key * 37) % 23) + dec!("0.5")
+    }
+
+    fn helper_with_entries(keys: &[i32]) -> (TestHelper, BTreeMap<i32, Decimal>) {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        let mut entries = BTreeMap::new();
+        for chunk in keys.chunks(10) {
+            for key in chunk.iter() {
+                helper.insert(*key, value_of(*key));
+                entries.insert(*key, value_of(*key));
+            }
+            helper.check_health();
+            helper.execute_expect_success(false);
+        }
+        (helper, entries)
+    }
+
+    #[test]
+    fn test_aggregate_empty_tree() {
+        let (mut helper, entries) = helper_with_entries(&[]);
+        assert_aggregates(&mut helper, &entries);
+    }
+
+    #[test]
+    fn test_aggregate_increasing_inserts() {
+        let keys: Vec<i32> = (0..40).collect();
+        let (mut helper, entries) = helper_with_entries(&keys);
+        assert_aggregates(&mut helper, &entries);
+    }
+
+    #[test]
+    fn test_aggregate_decreasing_inserts() {
+        let keys: Vec<i32> = (0..40).rev().collect();
+        let (mut helper, entries) = helper_with_entries(&keys);
+        assert_aggregates(&mut helper, &entries);
+    }
+
+    #[test]
+    fn test_aggregate_mixed_inserts() {
+        let keys: Vec<i32> = (0..40).map(|i| (i * 17) % 41).collect();
+        let (mut helper, entries) = helper_with_entries(&keys);
+        assert_aggregates(&mut helper, &entries);
+    }
+
+    #[test]
+    fn test_aggregate_after_overwrite() {
+        let keys: Vec<i32> = (0..20).collect();
+        let (mut helper, mut entries) = helper_with_entries(&keys);
+        for key in [3, 11, 19] {
+            helper.insert(key, dec!(100));
+            entries.insert(key, dec!(100));
+        }
+        helper.check_health();
+        helper.execute_expect_success(false);
+        assert_aggregates(&mut helper, &entries);
+    }
+
+    #[test]
+    fn test_aggregate_after_update() {
+        let keys: Vec<i32> = (0..20).collect();
+        let (mut helper, mut entries) = helper_with_entries(&keys);
+        for key in [0, 8, 15] {
+            helper.update_value(key, dec!(42));
+            entries.insert(key, dec!(42));
+        }
+        // Updating a missing key does not change anything.
+        helper.update_value(100, dec!(42));
+        helper.check_health();
+        helper.execute_expect_success(false);
+        assert_aggregates(&mut helper, &entries);
+    }
+
+    #[test]
+    fn test_aggregate_after_deletes() {
+        let keys: Vec<i32> = (0..40).map(|i| (i * 17) % 41).collect();
+        let (mut helper, mut entries) = helper_with_entries(&keys);
+        let to_delete: Vec<i32> = (0..40).filter(|i| i % 3 != 1).collect();
+        for chunk in to_delete.chunks(10) {
+            for key in chunk.iter() {
+                helper.remove(*key);
+                entries.remove(key);
+            }
+            helper.check_health();
+            helper.execute_expect_success(false);
+        }
+        assert_aggregates(&mut helper, &entries);
+    }
+
+    #[test]
+    fn test_aggregate_delete_all() {
+        let keys: Vec<i32> = (0..15).collect();
+        let (mut helper, mut entries) = helper_with_entries(&keys);
+        for key in keys.iter().rev() {
+            helper.remove(*key);
+            entries.remove(key);
+        }
+        helper.check_health();
+        helper.execute_expect_success(false);
+        assert_aggregates(&mut helper, &entries);
+    }
+}
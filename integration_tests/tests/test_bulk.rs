@@ -0,0 +1,98 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_bulk {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn helper_with_bulk_loaded_data(chunks: Vec<(i32, i32)>) -> TestHelper {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        let mut loaded = 0;
+        for (start, end) in chunks {
+            helper.bulk_load(start, end);
+            helper.check_health();
+            let receipt = helper.execute_expect_success(false);
+            let output: Vec<u64> = receipt.outputs("bulk_load");
+            loaded += (end - start) as u64;
+            assert_eq!(output, vec![loaded]);
+        }
+        helper
+    }
+
+    #[test]
+    fn test_bulk_load_in_multiple_transactions() {
+        let mut helper = helper_with_bulk_loaded_data(vec![(0, 10), (10, 35), (35, 36), (36, 100)]);
+        let expected: Vec<i32> = (0..100).collect();
+        helper.get_range_success(i32::MIN, i32::MAX, to_key_values(&expected), true);
+    }
+
+    #[test]
+    fn test_bulk_load_empty_chunk() {
+        let mut helper = helper_with_bulk_loaded_data(vec![(0, 10), (10, 10), (10, 20)]);
+        let expected: Vec<i32> = (0..20).collect();
+        helper.get_range_success(i32::MIN, i32::MAX, to_key_values(&expected), true);
+    }
+
+    #[test]
+    fn test_bulk_load_after_tree_changed_in_between() {
+        let mut helper = helper_with_bulk_loaded_data(vec![(0, 10)]);
+        helper.insert(50, 50);
+        helper.remove(9);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.bulk_load(60, 70);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        let mut expected: Vec<i32> = (0..9).collect();
+        expected.push(50);
+        expected.extend(60..70);
+        helper.get_range_success(i32::MIN, i32::MAX, to_key_values(&expected), true);
+    }
+
+    #[test]
+    fn test_bulk_load_unsorted_fails() {
+        let mut helper = helper_with_bulk_loaded_data(vec![(0, 10)]);
+        helper.bulk_load(5, 15);
+        helper.execute_expect_failure(false);
+        let expected: Vec<i32> = (0..10).collect();
+        helper.get_range_success(i32::MIN, i32::MAX, to_key_values(&expected), true);
+    }
+
+    #[test]
+    fn test_bulk_delete_in_multiple_transactions() {
+        let mut helper = helper_with_bulk_loaded_data(vec![(0, 50)]);
+        for i in 0..4 {
+            helper.bulk_delete(15);
+            helper.check_health();
+            let receipt = helper.execute_expect_success(false);
+            let output: Vec<bool> = receipt.outputs("bulk_delete");
+            assert_eq!(output, vec![i == 3]);
+            let expected: Vec<i32> = ((15 * (i + 1)).min(50)..50).collect();
+            helper.get_range_success(i32::MIN, i32::MAX, to_key_values(&expected), true);
+        }
+    }
+
+    #[test]
+    fn test_bulk_delete_after_tree_changed_in_between() {
+        let mut helper = helper_with_bulk_loaded_data(vec![(0, 20)]);
+        helper.bulk_delete(5);
+        helper.execute_expect_success(false);
+        helper.insert(-10, -10);
+        helper.remove(7);
+        helper.execute_expect_success(false);
+        helper.bulk_delete(5);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        let expected: Vec<i32> = (10..20).collect();
+        helper.get_range_success(i32::MIN, i32::MAX, to_key_values(&expected), true);
+        helper.bulk_delete(100);
+        helper.check_health();
+        let receipt = helper.execute_expect_success(false);
+        let output: Vec<bool> = receipt.outputs("bulk_delete");
+        assert_eq!(output, vec![true]);
+        helper.get_range_success(i32::MIN, i32::MAX, vec![], true);
+    }
+}
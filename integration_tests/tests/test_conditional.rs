@@ -0,0 +1,74 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_conditional {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_insert_if_absent() {
+        let mut helper = helper_with_keys(&[]);
+        for key in [5, 3, 8, 1, 4, 7, 9] {
+            helper.insert_if_absent_success(key, key * 10, Ok(()));
+        }
+        helper.insert_if_absent_success(4, 41, Err(41));
+        helper.insert_if_absent_success(9, 91, Err(91));
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(
+            0,
+            10,
+            vec![
+                (1, 10),
+                (3, 30),
+                (4, 40),
+                (5, 50),
+                (7, 70),
+                (8, 80),
+                (9, 90),
+            ],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_compare_and_swap() {
+        let mut helper = helper_with_keys(&[1, 2, 3]);
+        helper.compare_and_swap_success(2, 20, 21, Ok(20));
+        helper.compare_and_swap_success(2, 20, 22, Err(Some(21)));
+        helper.compare_and_swap_success(2, 21, 22, Ok(21));
+        helper.compare_and_swap_success(4, 40, 41, Err(None));
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(0, 10, vec![(1, 10), (2, 22), (3, 30)], false);
+    }
+
+    #[test]
+    fn test_update_replaces_value() {
+        let mut helper = helper_with_keys(&[1, 2, 3]);
+        helper.withdraw_success(2, 5, Some(20));
+        helper.withdraw_success(2, 5, Some(15));
+        helper.withdraw_success(4, 5, None);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(0, 10, vec![(1, 10), (2, 10), (3, 30)], false);
+    }
+
+    #[test]
+    fn test_update_removes_key() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_keys(&keys);
+        for key in [7, 0, 19, 10, 3, 8] {
+            helper.withdraw_success(key, key * 10, Some(key * 10));
+            helper.check_health();
+            helper.execute_expect_success(false);
+        }
+        let expected: Vec<(i32, i32)> = keys
+            .iter()
+            .filter(|key| ![7, 0, 19, 10, 3, 8].contains(*key))
+            .map(|key| (*key, *key * 10))
+            .collect();
+        helper.get_range_success(i32::MIN, i32::MAX, expected, false);
+    }
+}
@@ -0,0 +1,85 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_double_ended {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn alternating_success(
+        helper: &mut TestHelper,
+        key1: i32,
+        key2: i32,
+        back_first: bool,
+        expected: Vec<i32>,
+    ) {
+        let receipt = helper
+            .get_range_alternating(key1, key2, back_first)
+            .execute_expect_success(false);
+        let output: Vec<Vec<i32>> = receipt.outputs("get_range_alternating");
+        assert_eq!(output, vec![expected]);
+    }
+
+    #[test]
+    fn test_range_rev() {
+        let keys: Vec<i32> = vec![5, 1, 9, 3, 7, 2, 8];
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        let receipt = helper.get_range_rev(2, 9).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_rev");
+        assert_eq!(output, vec![to_key_values(&vec![8, 7, 5, 3, 2])]);
+    }
+
+    #[test]
+    fn test_range_back_rev() {
+        let keys: Vec<i32> = vec![5, 1, 9, 3, 7, 2, 8];
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        let receipt = helper
+            .get_range_back_rev(2, 9)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_back_rev");
+        assert_eq!(output, vec![to_key_values(&vec![2, 3, 5, 7, 8])]);
+    }
+
+    #[test]
+    fn test_range_rev_bounds_not_in_tree() {
+        let keys: Vec<i32> = (0..20).map(|i| i * 2).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        let receipt = helper.get_range_rev(5, 15).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_rev");
+        assert_eq!(output, vec![to_key_values(&vec![14, 12, 10, 8, 6])]);
+    }
+
+    #[test]
+    fn test_range_alternating_odd_count() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        alternating_success(&mut helper, 3, 8, false, vec![3, 7, 4, 6, 5]);
+        alternating_success(&mut helper, 3, 8, true, vec![7, 3, 6, 4, 5]);
+    }
+
+    #[test]
+    fn test_range_alternating_even_count() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        alternating_success(&mut helper, 3, 9, false, vec![3, 8, 4, 7, 5, 6]);
+        alternating_success(&mut helper, 3, 9, true, vec![8, 3, 7, 4, 6, 5]);
+    }
+
+    #[test]
+    fn test_range_alternating_single_and_empty() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        alternating_success(&mut helper, 4, 5, false, vec![4]);
+        alternating_success(&mut helper, 4, 5, true, vec![4]);
+        alternating_success(&mut helper, 30, 40, false, vec![]);
+        alternating_success(&mut helper, 30, 40, true, vec![]);
+    }
+
+    #[test]
+    fn test_range_alternating_full_tree() {
+        let keys: Vec<i32> = vec![5, 1, 9, 3, 7];
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        alternating_success(&mut helper, i32::MIN, i32::MAX, false, vec![1, 9, 3, 7, 5]);
+    }
+}
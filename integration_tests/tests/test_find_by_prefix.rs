@@ -0,0 +1,93 @@
+mod helper_avl_tree_augmented;
+
+#[cfg(test)]
+mod avltree_find_by_prefix {
+    use super::*;
+    use helper_avl_tree_augmented::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+    use std::collections::BTreeMap;
+
+    fn helper_with_levels(levels: &[(i32, Decimal)]) -> (TestHelper, BTreeMap<i32, Decimal>) {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        let mut book = BTreeMap::new();
+        for chunk in levels.chunks(10) {
+            for (price, quantity) in chunk.iter() {
+                helper.insert_level(*price, *quantity);
+                book.insert(*price, *quantity);
+            }
+            helper.execute_expect_success(false);
+        }
+        (helper, book)
+    }
+
+    fn assert_thresholds(
+        helper: &mut TestHelper,
+        book: &BTreeMap<i32, Decimal>,
+        thresholds: &[Decimal],
+    ) {
+        for threshold in thresholds {
+            for from_back in [false, true] {
+                helper.find_by_prefix_success(
+                    *threshold,
+                    from_back,
+                    expected_prefix_search(book, *threshold, from_back),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_by_prefix_simple() {
+        let levels: Vec<(i32, Decimal)> = (1..=4).map(|p| (p, dec!(10))).collect();
+        let (mut helper, _) = helper_with_levels(&levels);
+        helper.find_by_prefix_success(dec!(25), false, Some((3, dec!(20), dec!(5))));
+        helper.find_by_prefix_success(dec!(25), true, Some((2, dec!(20), dec!(5))));
+        helper.find_by_prefix_success(dec!(20), false, Some((2, dec!(10), dec!(10))));
+        helper.find_by_prefix_success(dec!(40), false, Some((4, dec!(30), dec!(10))));
+        helper.find_by_prefix_success(dec!("40.1"), false, None);
+        helper.find_by_prefix_success(dec!(0), false, Some((1, dec!(0), dec!(0))));
+        helper.find_by_prefix_success(dec!(0), true, Some((4, dec!(0), dec!(0))));
+    }
+
+    #[test]
+    fn test_find_by_prefix_empty() {
+        let (mut helper, _) = helper_with_levels(&[]);
+        helper.find_by_prefix_success(dec!(1), false, None);
+        helper.find_by_prefix_success(dec!(1), true, None);
+    }
+
+    #[test]
+    fn test_find_by_prefix_many_levels() {
+        let levels: Vec<(i32, Decimal)> = (0..40)
+            .map(|i| ((i * 17) % 41, Decimal::from((i * 7) % 11 + 1)))
+            .collect();
+        let (mut helper, book) = helper_with_levels(&levels);
+        let thresholds: Vec<Decimal> = vec![
+            dec!(1),
+            dec!("6.5"),
+            dec!(37),
+            dec!(100),
+            dec!(150),
+            dec!(231),
+            dec!(1000),
+        ];
+        assert_thresholds(&mut helper, &book, &thresholds);
+    }
+
+    #[test]
+    fn test_find_by_prefix_after_removing_levels() {
+        let levels: Vec<(i32, Decimal)> = (0..30).map(|i| (i, Decimal::from(i % 4 + 1))).collect();
+        let (mut helper, mut book) = helper_with_levels(&levels);
+        for price in [0, 5, 6, 7, 20, 29] {
+            helper.remove_level(price);
+            book.remove(&price);
+        }
+        helper.insert_level(3, dec!(50));
+        book.insert(3, dec!(50));
+        helper.execute_expect_success(false);
+        let thresholds: Vec<Decimal> = vec![dec!(2), dec!(30), dec!(55), dec!(60), dec!(90)];
+        assert_thresholds(&mut helper, &book, &thresholds);
+    }
+}
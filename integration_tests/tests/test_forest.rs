@@ -0,0 +1,192 @@
+mod helper_avl_tree_forest;
+
+#[cfg(test)]
+mod avltree_forest {
+    use super::*;
+    use helper_avl_tree_forest::*;
+    use scrypto_avltree::avl_tree_error::AvlTreeError;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn helper_with_trees(count: u64) -> TestHelper {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        for tree in 0..count {
+            helper.create_tree_success(tree);
+        }
+        helper
+    }
+
+    fn insert_all(helper: &mut TestHelper, tree: u64, entries: &[(i32, i32)]) {
+        for chunk in entries.chunks(10) {
+            for (key, value) in chunk.iter() {
+                helper.insert(tree, *key, *value);
+            }
+            helper.execute_expect_success(false);
+        }
+    }
+
+    #[test]
+    fn test_trees_with_interleaved_keys_are_independent() {
+        let mut helper = helper_with_trees(3);
+        let even: Vec<(i32, i32)> = (0..8).map(|key| (key * 2, key * 2)).collect();
+        let odd: Vec<(i32, i32)> = (0..8).map(|key| (key * 2 + 1, key * 20 + 10)).collect();
+        insert_all(&mut helper, 0, &even);
+        insert_all(&mut helper, 1, &odd);
+        helper.assert_tree(0, &even);
+        helper.assert_tree(1, &odd);
+        helper.assert_tree(2, &vec![]);
+        helper.get_success(0, 4, Some(4));
+        helper.get_success(1, 4, None);
+        helper.get_success(0, 5, None);
+        helper.get_success(1, 5, Some(50));
+        helper.get_success(2, 5, None);
+        // The bounds are keys of the other tree.
+        helper.get_range_success(0, 5, 11, vec![(6, 6), (8, 8), (10, 10)]);
+        helper.get_range_success(1, 4, 8, vec![(5, 50), (7, 70)]);
+        helper.get_range_success(1, 6, 7, vec![]);
+        helper.get_range_success(2, 0, 20, vec![]);
+    }
+
+    #[test]
+    fn test_insert_key_of_other_tree_fails() {
+        let mut helper = helper_with_trees(2);
+        insert_all(&mut helper, 0, &[(1, 1), (2, 2)]);
+        insert_all(&mut helper, 1, &[(3, 3)]);
+        helper.insert(1, 2, 20);
+        helper.execute_expect_failure(false);
+        helper.assert_tree(0, &vec![(1, 1), (2, 2)]);
+        helper.assert_tree(1, &vec![(3, 3)]);
+    }
+
+    #[test]
+    fn test_insert_replace_and_update() {
+        let mut helper = helper_with_trees(2);
+        insert_all(&mut helper, 0, &[(1, 1), (2, 2)]);
+        insert_all(&mut helper, 1, &[(3, 10)]);
+        helper.insert_success(0, 2, 20, Some(2));
+        helper.insert_success(1, 4, 30, None);
+        helper.update_value_success(1, 3, 100, Some(10));
+        helper.update_value_success(0, 3, 100, None);
+        helper.assert_tree(0, &vec![(1, 1), (2, 20)]);
+        helper.assert_tree(1, &vec![(3, 100), (4, 30)]);
+    }
+
+    #[test]
+    fn test_remove_head_and_tail() {
+        let mut helper = helper_with_trees(2);
+        let mut entries: Vec<(i32, i32)> = (0..10).map(|key| (key, key)).collect();
+        insert_all(&mut helper, 0, &entries);
+        insert_all(&mut helper, 1, &[(-1, -1), (20, 20)]);
+        helper.remove_success(0, 0, Some(0));
+        helper.remove_success(0, 9, Some(9));
+        helper.remove_success(0, 9, None);
+        helper.remove_success(0, 20, None);
+        entries.retain(|(key, _)| *key != 0 && *key != 9);
+        helper.assert_tree(0, &entries);
+        helper.assert_tree(1, &vec![(-1, -1), (20, 20)]);
+        for (key, value) in entries.iter() {
+            helper.remove_success(0, *key, Some(*value));
+        }
+        helper.assert_tree(0, &vec![]);
+        helper.assert_tree(1, &vec![(-1, -1), (20, 20)]);
+        insert_all(&mut helper, 0, &[(5, 5)]);
+        helper.assert_tree(0, &vec![(5, 5)]);
+    }
+
+    #[test]
+    fn test_interleaved_trees_with_rebalancing() {
+        let mut helper = helper_with_trees(2);
+        let mut expected: Vec<Vec<(i32, i32)>> = vec![vec![], vec![]];
+        let keys: Vec<i32> = (0..40).map(|index| (index * 17) % 40).collect();
+        for chunk in keys.chunks(10) {
+            for key in chunk.iter() {
+                let tree = (*key % 2) as usize;
+                helper.insert(tree as u64, *key, *key * 10);
+                expected[tree].push((*key, *key * 10));
+            }
+            helper.execute_expect_success(false);
+        }
+        for chunk in keys.iter().step_by(3).collect::<Vec<_>>().chunks(10) {
+            for key in chunk.iter() {
+                let tree = (**key % 2) as usize;
+                helper.remove(tree as u64, **key);
+                expected[tree].retain(|(other, _)| *other != **key);
+            }
+            helper.execute_expect_success(false);
+        }
+        for (tree, entries) in expected.iter_mut().enumerate() {
+            entries.sort();
+            helper.assert_tree(tree as u64, entries);
+        }
+    }
+
+    #[test]
+    fn test_move_entry() {
+        let mut helper = helper_with_trees(2);
+        insert_all(&mut helper, 0, &[(1, 1), (2, 2), (3, 3)]);
+        insert_all(&mut helper, 1, &[(4, 40)]);
+        helper.move_entry_success(0, 1, 1, Ok(()));
+        helper.move_entry_success(0, 1, 1, Err(AvlTreeError::KeyNotFound { key: 1 }));
+        helper.move_entry_success(0, 1, 4, Err(AvlTreeError::KeyNotFound { key: 4 }));
+        helper.assert_tree(0, &vec![(2, 2), (3, 3)]);
+        helper.assert_tree(1, &vec![(1, 1), (4, 40)]);
+        helper.move_entry_success(1, 0, 4, Ok(()));
+        helper.assert_tree(0, &vec![(2, 2), (3, 3), (4, 40)]);
+        helper.assert_tree(1, &vec![(1, 1)]);
+        helper.move_entry(0, 5, 2);
+        helper.execute_expect_failure(false);
+        helper.has_tree_success(5, false);
+        helper.assert_tree(0, &vec![(2, 2), (3, 3), (4, 40)]);
+    }
+
+    #[test]
+    fn test_move_range_splits_and_merges() {
+        let mut helper = helper_with_trees(3);
+        let entries: Vec<(i32, i32)> = (0..60).map(|key| (key, key * 10)).collect();
+        insert_all(&mut helper, 0, &entries);
+        insert_all(&mut helper, 1, &[(65, 1), (70, 1)]);
+        // Split off the upper half into an empty tree.
+        helper.move_range_success(0, 2, 30, 60);
+        helper.assert_tree(0, &entries[..30].to_vec());
+        helper.assert_tree(2, &entries[30..].to_vec());
+        // Merge into a tree with bigger keys.
+        helper.move_range_success(0, 1, 3, 25);
+        let mut expected: Vec<(i32, i32)> = entries[3..25].to_vec();
+        expected.extend([(65, 1), (70, 1)]);
+        helper.assert_tree(1, &expected);
+        let mut rest = entries[..3].to_vec();
+        rest.extend(entries[25..30].iter().cloned());
+        helper.assert_tree(0, &rest);
+        helper.move_range_success(0, 1, 100, 200);
+        helper.assert_tree(0, &rest);
+        // Merge into a tree with smaller keys.
+        helper.move_range_success(2, 0, i32::MIN, i32::MAX);
+        helper.assert_tree(2, &vec![]);
+        rest.extend(entries[30..].iter().cloned());
+        helper.assert_tree(0, &rest);
+    }
+
+    #[test]
+    fn test_move_range_between_interleaved_trees() {
+        let mut helper = helper_with_trees(2);
+        let even: Vec<(i32, i32)> = (0..20).map(|key| (key * 2, key)).collect();
+        let odd: Vec<(i32, i32)> = (0..20).map(|key| (key * 2 + 1, key)).collect();
+        insert_all(&mut helper, 0, &even);
+        insert_all(&mut helper, 1, &odd);
+        // The moved keys are between the keys of the target tree, so the nodes are linked one by one.
+        helper.move_range_success(0, 1, 10, 31);
+        let mut expected: Vec<(i32, i32)> = odd.clone();
+        expected.extend(even[5..16].iter().cloned());
+        expected.sort();
+        helper.assert_tree(1, &expected);
+        let mut rest = even[..5].to_vec();
+        rest.extend(even[16..].iter().cloned());
+        helper.assert_tree(0, &rest);
+        helper.move_range_success(1, 0, i32::MIN, i32::MAX);
+        helper.assert_tree(1, &vec![]);
+        let mut all: Vec<(i32, i32)> = even.clone();
+        all.extend(odd.iter().cloned());
+        all.sort();
+        helper.assert_tree(0, &all);
+    }
+}
@@ -0,0 +1,74 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_hint {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_insert_with_hint_next_to_hint() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        let mut helper = helper_with_keys(&keys);
+        let mut expected = keys.clone();
+        // Clustered inserts around a few keys, using the previous key as hint.
+        for start in [0, 70, 190] {
+            let mut hint = start;
+            for key in start + 1..start + 6 {
+                helper.insert_with_hint(hint, key, key * 10);
+                expected.push(key);
+                hint = key;
+            }
+            helper.check_health();
+            helper.execute_expect_success(false);
+        }
+        expected.sort();
+        let expected = expected.iter().map(|key| (*key, *key * 10)).collect();
+        helper.get_range_success(i32::MIN, i32::MAX, expected, false);
+    }
+
+    #[test]
+    fn test_insert_with_hint_far_or_missing() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        let mut helper = helper_with_keys(&keys);
+        let mut expected = keys.clone();
+        for (hint, key) in [(0, 185), (190, -5), (55, 56), (100, 95), (95, 105)] {
+            helper.insert_with_hint(hint, key, key * 10);
+            expected.push(key);
+        }
+        helper.check_health();
+        helper.execute_expect_success(false);
+        expected.sort();
+        let expected = expected.iter().map(|key| (*key, *key * 10)).collect();
+        helper.get_range_success(i32::MIN, i32::MAX, expected, false);
+    }
+
+    #[test]
+    fn test_insert_with_hint_existing_key() {
+        let mut helper = helper_with_keys(&[10, 20, 30]);
+        helper.insert_with_hint(10, 30, 31);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(
+            i32::MIN,
+            i32::MAX,
+            vec![(10, 100), (20, 200), (30, 31)],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_get_near() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.get_near_success(0, 50, Some((50, 500)));
+        helper.get_near_success(40, 51, Some((60, 600)));
+        helper.get_near_success(60, 51, Some((60, 600)));
+        helper.get_near_success(190, 1, Some((10, 100)));
+        helper.get_near_success(0, 189, Some((190, 1900)));
+        helper.get_near_success(0, -5, Some((0, 0)));
+        helper.get_near_success(55, 75, Some((80, 800)));
+        helper.get_near_success(100, 191, None);
+        helper.get_near_success(190, 191, None);
+    }
+}
@@ -0,0 +1,116 @@
+mod helper_avl_tree_interval;
+
+#[cfg(test)]
+mod avltree_interval {
+    use super::*;
+    use helper_avl_tree_interval::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    /// Intervals with their index as id.
+    fn helper_with_intervals(intervals: &[(i32, i32)]) -> TestHelper {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        for (chunk_index, chunk) in intervals.chunks(10).enumerate() {
+            for (index, (lo, hi)) in chunk.iter().enumerate() {
+                helper.insert(*lo, *hi, (chunk_index * 10 + index) as u32);
+            }
+            helper.check_health();
+            helper.execute_expect_success(false);
+        }
+        helper
+    }
+
+    /// All intervals `[lo, hi)` with `lo < end` and `hi > start`, sorted like the tree.
+    fn expected_overlapping(
+        intervals: &[(i32, i32)],
+        start: i32,
+        end_included: i32,
+    ) -> Vec<(i32, i32, u32)> {
+        let mut expected: Vec<(i32, i32, u32)> = intervals
+            .iter()
+            .enumerate()
+            .filter(|(_, (lo, hi))| *lo <= end_included && *hi > start)
+            .map(|(index, (lo, hi))| (*lo, *hi, index as u32))
+            .collect();
+        expected.sort();
+        expected
+    }
+
+    fn intervals() -> Vec<(i32, i32)> {
+        (0..40)
+            .map(|i| {
+                let lo = (i * 17) % 50;
+                (lo, lo + 1 + (i * 7) % 12)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stab() {
+        let intervals = vec![(1, 5), (3, 4), (4, 9), (10, 12)];
+        let mut helper = helper_with_intervals(&intervals);
+        helper.stab_success(0, vec![]);
+        helper.stab_success(1, vec![(1, 5, 0)]);
+        helper.stab_success(3, vec![(1, 5, 0), (3, 4, 1)]);
+        helper.stab_success(4, vec![(1, 5, 0), (4, 9, 2)]);
+        helper.stab_success(9, vec![]);
+        helper.stab_success(11, vec![(10, 12, 3)]);
+    }
+
+    #[test]
+    fn test_stab_many() {
+        let intervals = intervals();
+        let mut helper = helper_with_intervals(&intervals);
+        for point in (-1..65).step_by(4) {
+            helper.stab_success(point, expected_overlapping(&intervals, point, point));
+        }
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let intervals = intervals();
+        let mut helper = helper_with_intervals(&intervals);
+        for (start, end) in [(0, 5), (10, 11), (20, 35), (48, 70), (-10, 0), (70, 80)] {
+            helper.overlapping_success(
+                start,
+                end,
+                expected_overlapping(&intervals, start, end - 1),
+            );
+            helper.overlapping_both_included_success(
+                start,
+                end,
+                expected_overlapping(&intervals, start, end),
+            );
+        }
+    }
+
+    #[test]
+    fn test_overlapping_after_removes() {
+        let mut intervals = intervals();
+        let mut helper = helper_with_intervals(&intervals);
+        let removed: Vec<usize> = (0..40).step_by(3).collect();
+        for chunk in removed.chunks(5) {
+            for index in chunk.iter() {
+                let (lo, hi) = intervals[*index];
+                helper.remove(lo, hi);
+            }
+            helper.execute_expect_success(false);
+        }
+        helper.check_health();
+        helper.execute_expect_success(false);
+        for index in removed {
+            intervals[index] = (i32::MAX - 1, i32::MAX);
+        }
+        helper.overlapping_success(0, 60, expected_overlapping(&intervals, 0, 59));
+        helper.stab_success(30, expected_overlapping(&intervals, 30, 30));
+        helper.get_success(intervals[1].0, intervals[1].1, Some(1));
+        helper.get_success(17, 26, None);
+    }
+
+    #[test]
+    fn test_empty_interval_fails() {
+        let mut helper = helper_with_intervals(&[]);
+        helper.insert(5, 5, 0);
+        helper.execute_expect_failure(false);
+    }
+}
@@ -0,0 +1,54 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_keys {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_keys_full_range() {
+        let keys: Vec<i32> = vec![5, 1, 9, 3, 7, 2, 8];
+        let mut helper = helper_with_keys(&keys);
+        helper.get_keys_success(i32::MIN, i32::MAX, vec![1, 2, 3, 5, 7, 8, 9]);
+        helper.get_keys_back_success(i32::MIN, i32::MAX, vec![9, 8, 7, 5, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_keys_bounds_between_keys() {
+        let keys: Vec<i32> = (0..50).map(|i| i * 2).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.get_keys_success(11, 21, vec![12, 14, 16, 18, 20]);
+        helper.get_keys_back_success(11, 21, vec![20, 18, 16, 14, 12]);
+    }
+
+    #[test]
+    fn test_keys_end_excluded() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.get_keys_success(5, 10, vec![5, 6, 7, 8, 9]);
+        helper.get_keys_back_success(5, 10, vec![9, 8, 7, 6, 5]);
+    }
+
+    #[test]
+    fn test_keys_empty() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.get_keys_success(30, 40, vec![]);
+        helper.get_keys_back_success(30, 40, vec![]);
+        helper.get_keys_success(5, 5, vec![]);
+        helper.get_keys_back_success(5, 5, vec![]);
+    }
+
+    #[test]
+    fn test_keys_after_remove() {
+        let keys: Vec<i32> = (0..10).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.remove(3);
+        helper.remove(7);
+        helper.execute_expect_success(false);
+        helper.get_keys_success(0, 10, vec![0, 1, 2, 4, 5, 6, 8, 9]);
+        helper.get_keys_back_success(0, 10, vec![9, 8, 6, 5, 4, 2, 1, 0]);
+    }
+}
@@ -0,0 +1,121 @@
+mod helper_avl_tree_lazy;
+
+#[cfg(test)]
+mod avltree_lazy {
+    use super::*;
+    use helper_avl_tree_lazy::*;
+    use scrypto::prelude::*;
+
+    fn model_with_keys(keys: &[i32]) -> LazyModel {
+        let mut model = LazyModel::new();
+        for chunk in keys.chunks(10) {
+            for key in chunk.iter() {
+                model.insert(*key, Decimal::from(*key));
+            }
+            model.execute_and_compare();
+        }
+        model
+    }
+
+    #[test]
+    fn test_range_apply_add() {
+        let keys: Vec<i32> = (0..30).collect();
+        let mut model = model_with_keys(&keys);
+        model.range_apply(10, 15, dec!(1), dec!(100));
+        model.execute_and_compare();
+        model.range_apply_both_included(0, 29, dec!(1), dec!(1));
+        model.execute_and_compare();
+    }
+
+    #[test]
+    fn test_range_apply_not_commuting_updates() {
+        let keys: Vec<i32> = (0..30).map(|i| (i * 7) % 31).collect();
+        let mut model = model_with_keys(&keys);
+        model.range_apply(5, 25, dec!(2), dec!(1));
+        model.range_apply(0, 12, dec!(1), dec!(3));
+        model.range_apply(10, 40, dec!(-1), dec!(0));
+        model.execute_and_compare();
+        model.range_apply_both_included(7, 7, dec!(3), dec!(-2));
+        model.range_apply(i32::MIN, i32::MAX, dec!(2), dec!(5));
+        model.execute_and_compare();
+    }
+
+    #[test]
+    fn test_range_apply_outside_and_empty() {
+        let keys: Vec<i32> = (0..10).collect();
+        let mut model = model_with_keys(&keys);
+        model.range_apply(20, 30, dec!(2), dec!(1));
+        model.range_apply(-10, -1, dec!(2), dec!(1));
+        model.range_apply(5, 5, dec!(2), dec!(1));
+        model.execute_and_compare();
+    }
+
+    #[test]
+    fn test_range_apply_then_inserts() {
+        let keys: Vec<i32> = (0..20).map(|i| i * 2).collect();
+        let mut model = model_with_keys(&keys);
+        model.range_apply(0, 30, dec!(2), dec!(1));
+        model.range_apply(10, 40, dec!(1), dec!(7));
+        model.execute_and_compare();
+        // Inserts below tagged subtrees must not get the pending updates and rotate the tagged nodes.
+        let new_keys: Vec<i32> = (0..20).map(|i| i * 2 + 1).collect();
+        for chunk in new_keys.chunks(5) {
+            for key in chunk.iter() {
+                model.insert(*key, dec!(1000));
+            }
+            model.execute_and_compare();
+            model.range_apply(chunk[0], chunk[0] + 15, dec!(-1), dec!(2));
+        }
+        model.execute_and_compare();
+    }
+
+    #[test]
+    fn test_range_apply_then_removes() {
+        let keys: Vec<i32> = (0..40).map(|i| (i * 17) % 41).collect();
+        let mut model = model_with_keys(&keys);
+        model.range_apply(0, 30, dec!(2), dec!(1));
+        model.range_apply(10, 41, dec!(1), dec!(-4));
+        model.execute_and_compare();
+        let to_remove: Vec<i32> = (0..40).filter(|i| i % 3 != 0).collect();
+        for chunk in to_remove.chunks(6) {
+            for key in chunk.iter() {
+                model.remove(*key);
+            }
+            model.execute_and_compare();
+            model.range_apply(chunk[0] - 5, chunk[0] + 10, dec!(3), dec!(1));
+        }
+        model.execute_and_compare();
+    }
+
+    #[test]
+    fn test_range_apply_then_remove_left_heavy_node() {
+        // Node 10 is left heavy and its predecessor 9 is four levels below it,
+        // so the delete replaces it by the predecessor and rotates below the path to it.
+        let keys: Vec<i32> = (0..40).map(|i| (i * 17) % 41).collect();
+        let mut model = model_with_keys(&keys);
+        model.range_apply(0, 30, dec!(2), dec!(1));
+        model.range_apply(5, 12, dec!(1), dec!(-4));
+        model.range_apply_both_included(9, 9, dec!(3), dec!(1));
+        model.execute_and_compare();
+        model.remove(10);
+        model.execute_and_compare();
+        model.range_apply(0, 20, dec!(-1), dec!(2));
+        model.remove(9);
+        model.remove(6);
+        model.execute_and_compare();
+    }
+
+    #[test]
+    fn test_range_apply_with_overwrites_and_updates() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut model = model_with_keys(&keys);
+        model.range_apply(0, 20, dec!(2), dec!(0));
+        model.insert(5, dec!(50));
+        model.update_value(12, dec!(-3));
+        model.update_value(100, dec!(1));
+        model.execute_and_compare();
+        model.range_apply(3, 13, dec!(1), dec!(10));
+        model.update_value(5, dec!(0));
+        model.execute_and_compare();
+    }
+}
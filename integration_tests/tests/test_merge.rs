@@ -0,0 +1,96 @@
+mod helper_avl_tree_merge;
+
+#[cfg(test)]
+mod avltree_merge {
+    use super::*;
+    use helper_avl_tree_merge::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    /// Inserts the keys of every tree with value = 100 * source + key.
+    fn helper_with_trees(trees: &[Vec<i32>]) -> TestHelper {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(trees.len() as u32, false);
+        for (source, keys) in trees.iter().enumerate() {
+            for chunk in keys.chunks(10) {
+                for key in chunk.iter() {
+                    helper.insert(source as u32, *key, 100 * source as i32 + *key);
+                }
+                helper.execute_expect_success(false);
+            }
+        }
+        helper
+    }
+
+    /// The merged entries of the range, with equal keys in order of the source.
+    fn expected_merge(trees: &[Vec<i32>], key1: i32, key2: i32) -> Vec<(u32, i32, i32)> {
+        let mut entries: Vec<(i32, u32, i32)> = trees
+            .iter()
+            .enumerate()
+            .flat_map(|(source, keys)| {
+                keys.iter()
+                    .filter(|key| **key >= key1 && **key < key2)
+                    .map(move |key| (*key, source as u32, 100 * source as i32 + *key))
+            })
+            .collect();
+        entries.sort();
+        entries
+            .into_iter()
+            .map(|(key, source, value)| (source, key, value))
+            .collect()
+    }
+
+    fn expected_merge_back(trees: &[Vec<i32>], key1: i32, key2: i32) -> Vec<(u32, i32, i32)> {
+        let mut entries = expected_merge(trees, key1, key2);
+        // Descending keys, equal keys still in order of the source.
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        entries
+    }
+
+    #[test]
+    fn test_merge_disjoint_and_shared_keys() {
+        let trees = vec![vec![1, 4, 7, 10], vec![2, 4, 8], vec![4, 5, 6, 10, 11]];
+        let mut helper = helper_with_trees(&trees);
+        helper.get_merged_success(
+            i32::MIN,
+            i32::MAX,
+            expected_merge(&trees, i32::MIN, i32::MAX),
+        );
+        helper.get_merged_back_success(
+            i32::MIN,
+            i32::MAX,
+            expected_merge_back(&trees, i32::MIN, i32::MAX),
+        );
+    }
+
+    #[test]
+    fn test_merge_range() {
+        let trees = vec![
+            (0..20).map(|i| i * 3).collect::<Vec<i32>>(),
+            (0..20).map(|i| i * 5).collect::<Vec<i32>>(),
+            vec![],
+            (0..10).map(|i| 40 - i).collect::<Vec<i32>>(),
+        ];
+        let mut helper = helper_with_trees(&trees);
+        helper.get_merged_success(10, 35, expected_merge(&trees, 10, 35));
+        helper.get_merged_back_success(10, 35, expected_merge_back(&trees, 10, 35));
+        helper.get_merged_success(200, 300, vec![]);
+    }
+
+    #[test]
+    fn test_merge_iterators_with_different_ranges() {
+        let trees = vec![vec![1, 2, 3, 4], vec![1, 2, 3, 4]];
+        let mut helper = helper_with_trees(&trees);
+        helper.get_merged_from_success(
+            vec![3, 2],
+            vec![(1, 2, 102), (0, 3, 3), (1, 3, 103), (0, 4, 4), (1, 4, 104)],
+        );
+    }
+
+    #[test]
+    fn test_merge_mixed_directions_fails() {
+        let trees = vec![vec![1], vec![2]];
+        let mut helper = helper_with_trees(&trees);
+        helper.get_merged_mixed_directions();
+        helper.execute_expect_failure(false);
+    }
+}
@@ -0,0 +1,108 @@
+mod helper_avl_tree_multi_map;
+
+#[cfg(test)]
+mod avltree_multi_map {
+    use super::*;
+    use helper_avl_tree_multi_map::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn helper_with_entries(entries: &[(i32, i32)]) -> TestHelper {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        for chunk in entries.chunks(10) {
+            for (key, value) in chunk.iter() {
+                helper.insert(*key, *value);
+            }
+            helper.check_health();
+            helper.execute_expect_success(false);
+        }
+        helper
+    }
+
+    #[test]
+    fn test_values_in_insertion_order() {
+        let entries = vec![(5, 1), (3, 2), (5, 3), (5, 4), (3, 5), (7, 6)];
+        let mut helper = helper_with_entries(&entries);
+        helper.get_all_success(5, vec![1, 3, 4]);
+        helper.get_all_success(3, vec![2, 5]);
+        helper.get_all_success(7, vec![6]);
+        helper.get_all_success(4, vec![]);
+        helper.contains_key_success(3, true);
+        helper.contains_key_success(4, false);
+    }
+
+    #[test]
+    fn test_range_over_duplicates() {
+        let entries = vec![(5, 1), (3, 2), (5, 3), (5, 4), (3, 5), (7, 6)];
+        let mut helper = helper_with_entries(&entries);
+        helper.get_range_success(
+            i32::MIN,
+            i32::MAX,
+            vec![(3, 2), (3, 5), (5, 1), (5, 3), (5, 4), (7, 6)],
+        );
+        helper.get_range_back_success(
+            i32::MIN,
+            i32::MAX,
+            vec![(7, 6), (5, 4), (5, 3), (5, 1), (3, 5), (3, 2)],
+        );
+        helper.get_range_success(4, 7, vec![(5, 1), (5, 3), (5, 4)]);
+        helper.get_range_success(3, 5, vec![(3, 2), (3, 5)]);
+        helper.get_range_back_success(5, 8, vec![(7, 6), (5, 4), (5, 3), (5, 1)]);
+        helper.get_range_next_keys_success(3, 6, vec![Some(3), Some(5), Some(5), Some(5), None]);
+    }
+
+    #[test]
+    fn test_remove_one_fifo() {
+        let entries = vec![(1, 10), (2, 20), (1, 11), (1, 12), (2, 21)];
+        let mut helper = helper_with_entries(&entries);
+        helper.remove_one_success(1, Some(10));
+        helper.remove_one_success(1, Some(11));
+        helper.insert(1, 13);
+        helper.execute_expect_success(false);
+        helper.get_all_success(1, vec![12, 13]);
+        helper.remove_one_success(1, Some(12));
+        helper.remove_one_success(1, Some(13));
+        helper.remove_one_success(1, None);
+        helper.get_range_success(i32::MIN, i32::MAX, vec![(2, 20), (2, 21)]);
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let entries: Vec<(i32, i32)> = (0..40).map(|i| (i % 4, i)).collect();
+        let mut helper = helper_with_entries(&entries);
+        helper.remove_all_success(2, vec![2, 6, 10, 14, 18, 22, 26, 30, 34, 38]);
+        helper.remove_all_success(2, vec![]);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.contains_key_success(2, false);
+        helper.get_all_success(3, vec![3, 7, 11, 15, 19, 23, 27, 31, 35, 39]);
+        let mut expected: Vec<(i32, i32)> = (0..40)
+            .map(|i| (i % 4, i))
+            .filter(|(key, _)| *key != 2)
+            .collect();
+        expected.sort();
+        helper.get_range_success(i32::MIN, i32::MAX, expected);
+    }
+
+    #[test]
+    fn test_many_values_per_key_with_rebalancing() {
+        let entries: Vec<(i32, i32)> = (0..30).map(|i| ((i * 7) % 5, i)).collect();
+        let mut helper = helper_with_entries(&entries);
+        for key in 0..5 {
+            let expected: Vec<i32> = entries
+                .iter()
+                .filter(|(k, _)| *k == key)
+                .map(|(_, v)| *v)
+                .collect();
+            helper.get_all_success(key, expected);
+        }
+        for _ in 0..3 {
+            helper.remove_one(0);
+            helper.remove_one(4);
+        }
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_all_success(0, vec![15, 20, 25]);
+        helper.get_all_success(4, vec![17, 22, 27]);
+    }
+}
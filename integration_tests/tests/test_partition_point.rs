@@ -0,0 +1,52 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_partition_point {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_partition_point_empty_tree() {
+        let mut helper = helper_with_keys(&[]);
+        helper.partition_point_by_key_success(0, None);
+        helper.partition_point_by_value_success(0, None);
+    }
+
+    #[test]
+    fn test_partition_point_by_key() {
+        let keys: Vec<i32> = (0..30).map(|i| i * 2).collect();
+        let mut helper = helper_with_keys(&keys);
+        for key in -1..60 {
+            let expected = keys.iter().find(|k| **k >= key).cloned();
+            helper.partition_point_by_key_success(key, expected);
+        }
+    }
+
+    #[test]
+    fn test_partition_point_by_value() {
+        let keys: Vec<i32> = (0..30).map(|i| (i * 7) % 30).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.partition_point_by_value_success(-1, Some(0));
+        helper.partition_point_by_value_success(0, Some(1));
+        helper.partition_point_by_value_success(125, Some(13));
+        helper.partition_point_by_value_success(130, Some(14));
+        helper.partition_point_by_value_success(289, Some(29));
+        helper.partition_point_by_value_success(290, None);
+    }
+
+    #[test]
+    fn test_partition_point_after_updates_and_removes() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_keys(&keys);
+        // Values stay monotone: 0, 10, ..., 80, 90, 95, 110, ...
+        helper.update_value(10, 95);
+        helper.remove(5);
+        helper.remove(6);
+        helper.execute_expect_success(false);
+        helper.partition_point_by_value_success(92, Some(10));
+        helper.partition_point_by_value_success(95, Some(11));
+        helper.partition_point_by_value_success(45, Some(7));
+        helper.partition_point_by_key_success(5, Some(7));
+    }
+}
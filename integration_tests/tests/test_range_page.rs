@@ -0,0 +1,67 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_range_page {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_range_page_forward() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        helper.start_range_page(3, 11, false);
+        helper.execute_expect_success(false);
+        helper.next_range_page_success(3, vec![3, 4, 5], true);
+        helper.next_range_page_success(3, vec![6, 7, 8], true);
+        helper.next_range_page_success(3, vec![9, 10], false);
+        helper.next_range_page_success(3, vec![], false);
+    }
+
+    #[test]
+    fn test_range_page_back() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        helper.start_range_page(3, 11, true);
+        helper.execute_expect_success(false);
+        helper.next_range_page_success(4, vec![10, 9, 8, 7], true);
+        helper.next_range_page_success(4, vec![6, 5, 4, 3], false);
+    }
+
+    #[test]
+    fn test_range_page_exact_end() {
+        let keys: Vec<i32> = (0..10).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        helper.start_range_page(i32::MIN, i32::MAX, false);
+        helper.execute_expect_success(false);
+        helper.next_range_page_success(5, vec![0, 1, 2, 3, 4], true);
+        helper.next_range_page_success(5, vec![5, 6, 7, 8, 9], false);
+    }
+
+    #[test]
+    fn test_range_page_tree_changed_in_between() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        helper.start_range_page(0, 15, false);
+        helper.execute_expect_success(false);
+        helper.next_range_page_success(5, vec![0, 1, 2, 3, 4], true);
+        // The next key of the cursor is removed and a new key is inserted inside the rest of the range.
+        helper.remove(5);
+        helper.remove(6);
+        helper.insert(100, 100);
+        helper.remove(12);
+        helper.execute_expect_success(false);
+        helper.next_range_page_success(5, vec![7, 8, 9, 10, 11], true);
+        helper.next_range_page_success(5, vec![13, 14], false);
+    }
+
+    #[test]
+    fn test_range_page_empty_range() {
+        let keys: Vec<i32> = (0..10).collect();
+        let mut helper = helper_with_key_values(&to_key_values(&keys));
+        helper.start_range_page(20, 30, false);
+        helper.execute_expect_success(false);
+        helper.next_range_page_success(5, vec![], false);
+    }
+}
@@ -0,0 +1,53 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_range_ref {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn expected(keys: Vec<i32>) -> Vec<(i32, i32)> {
+        keys.into_iter().map(|k| (k, k * 10)).collect()
+    }
+
+    #[test]
+    fn test_range_ref() {
+        let keys: Vec<i32> = vec![5, 1, 9, 3, 7, 2, 8];
+        let mut helper = helper_with_keys(&keys);
+        let receipt = helper.get_range_ref(2, 9).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_ref");
+        assert_eq!(output, vec![expected(vec![2, 3, 5, 7, 8])]);
+    }
+
+    #[test]
+    fn test_range_back_ref() {
+        let keys: Vec<i32> = vec![5, 1, 9, 3, 7, 2, 8];
+        let mut helper = helper_with_keys(&keys);
+        let receipt = helper
+            .get_range_back_ref(2, 9)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_back_ref");
+        assert_eq!(output, vec![expected(vec![8, 7, 5, 3, 2])]);
+    }
+
+    #[test]
+    fn test_range_ref_empty() {
+        let keys: Vec<i32> = (0..10).collect();
+        let mut helper = helper_with_keys(&keys);
+        let receipt = helper.get_range_ref(20, 30).execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_ref");
+        assert_eq!(output, vec![vec![]]);
+    }
+
+    #[test]
+    fn test_range_ref_held_at_the_same_time() {
+        let keys: Vec<i32> = (0..30).collect();
+        let mut helper = helper_with_keys(&keys);
+        let receipt = helper
+            .get_range_ref_held(5, 25)
+            .execute_expect_success(false);
+        let output: Vec<Vec<(i32, i32)>> = receipt.outputs("get_range_ref_held");
+        assert_eq!(output, vec![expected((5..25).collect())]);
+    }
+}
@@ -0,0 +1,73 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_rekey {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_avltree::avl_tree_error::AvlTreeError;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn rekey_all(helper: &mut TestHelper, moves: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        let mut expected: Vec<(i32, i32)> = (0..20).map(|key| (key * 10, key * 100)).collect();
+        for (old_key, new_key) in moves {
+            helper.rekey_success(*old_key, *new_key, Ok(()));
+            helper.check_health();
+            helper.execute_expect_success(false);
+            let entry = expected.iter_mut().find(|(key, _)| key == old_key).unwrap();
+            entry.0 = *new_key;
+        }
+        expected.sort();
+        expected
+    }
+
+    #[test]
+    fn test_rekey_between_neighbours() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        let mut helper = helper_with_keys(&keys);
+        // Root, inner nodes, leaves, head and tail keep their position in the tree.
+        let moves = [
+            (70, 75),
+            (50, 41),
+            (0, -5),
+            (190, 1000),
+            (100, 109),
+            (75, 71),
+        ];
+        let expected = rekey_all(&mut helper, &moves);
+        helper.get_range_success(i32::MIN, i32::MAX, expected, false);
+    }
+
+    #[test]
+    fn test_rekey_to_other_position() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        let mut helper = helper_with_keys(&keys);
+        let moves = [(30, 175), (0, 1000), (190, -5), (70, 71), (71, 185)];
+        let expected = rekey_all(&mut helper, &moves);
+        helper.get_range_success(i32::MIN, i32::MAX, expected, false);
+    }
+
+    #[test]
+    fn test_rekey_fails_cleanly() {
+        let mut helper = helper_with_keys(&[10, 20, 30]);
+        helper.rekey_success(10, 30, Err(AvlTreeError::KeyExists { key: 30 }));
+        helper.rekey_success(20, 20, Err(AvlTreeError::KeyExists { key: 20 }));
+        helper.rekey_success(15, 25, Err(AvlTreeError::KeyNotFound { key: 15 }));
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(
+            i32::MIN,
+            i32::MAX,
+            vec![(10, 100), (20, 200), (30, 300)],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_rekey_single_node() {
+        let mut helper = helper_with_keys(&[10]);
+        helper.rekey_success(10, 20, Ok(()));
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(i32::MIN, i32::MAX, vec![(20, 100)], false);
+    }
+}
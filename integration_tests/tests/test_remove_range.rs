@@ -0,0 +1,81 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_remove_range {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn key_values(keys: &[i32]) -> Vec<(i32, i32)> {
+        keys.iter().map(|key| (*key, *key * 10)).collect()
+    }
+
+    /// Removes the ranges one after another and checks the tree and the linked list in both directions.
+    fn remove_ranges(keys: &[i32], ranges: &[(i32, i32)]) {
+        let mut helper = helper_with_keys(keys);
+        let mut expected = keys.to_vec();
+        expected.sort();
+        for (start, end) in ranges {
+            let removed = expected
+                .iter()
+                .filter(|key| (*start..*end).contains(*key))
+                .count();
+            expected.retain(|key| !(*start..*end).contains(key));
+            helper.remove_range_success(*start, *end, removed as u64);
+            helper.check_health();
+            helper.execute_expect_success(false);
+            helper.get_range_success(i32::MIN, i32::MAX, key_values(&expected), false);
+            let mut back = key_values(&expected);
+            back.reverse();
+            helper.get_range_back_success(i32::MIN, i32::MAX, back, false);
+        }
+    }
+
+    #[test]
+    fn test_remove_range_inside() {
+        let keys: Vec<i32> = (0..40).collect();
+        remove_ranges(&keys, &[(10, 20), (25, 27), (5, 6), (21, 35)]);
+    }
+
+    #[test]
+    fn test_remove_range_at_borders() {
+        let keys: Vec<i32> = (0..40).collect();
+        remove_ranges(&keys, &[(i32::MIN, 3), (36, i32::MAX), (3, 4), (35, 36)]);
+    }
+
+    #[test]
+    fn test_remove_range_without_keys_in_it() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        remove_ranges(&keys, &[(11, 19), (15, 15), (30, 20), (200, 300), (-20, 0)]);
+    }
+
+    #[test]
+    fn test_remove_range_unbalanced_parts() {
+        let keys: Vec<i32> = (0..40).collect();
+        remove_ranges(&keys, &[(1, 38), (0, 1)]);
+        let keys: Vec<i32> = (0..40).rev().collect();
+        remove_ranges(&keys, &[(2, 39), (39, 40)]);
+    }
+
+    #[test]
+    fn test_remove_range_everything() {
+        let keys: Vec<i32> = (0..30).collect();
+        remove_ranges(&keys, &[(i32::MIN, i32::MAX), (0, 10)]);
+    }
+
+    #[test]
+    fn test_drain_range() {
+        let keys: Vec<i32> = (0..30).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.drain_range_success(12, 17, key_values(&[12, 13, 14, 15, 16]));
+        helper.drain_range_success(12, 17, vec![]);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        let expected: Vec<i32> = keys
+            .iter()
+            .copied()
+            .filter(|key| !(12..17).contains(key))
+            .collect();
+        helper.get_range_success(i32::MIN, i32::MAX, key_values(&expected), false);
+    }
+}
@@ -0,0 +1,119 @@
+mod helper_avl_tree_sequence;
+
+#[cfg(test)]
+mod avltree_sequence {
+    use super::*;
+    use helper_avl_tree_sequence::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn helper_with_list(values: &[i32]) -> TestHelper {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        for chunk in values.chunks(10) {
+            for value in chunk.iter() {
+                helper.push_back(*value);
+            }
+            helper.execute_expect_success(false);
+        }
+        helper
+    }
+
+    #[test]
+    fn test_push_back_and_front() {
+        let mut helper = helper_with_list(&[]);
+        helper.assert_list(&vec![]);
+        helper.push_back(2);
+        helper.push_back(3);
+        helper.push_front(1);
+        helper.push_front(0);
+        helper.execute_expect_success(false);
+        helper.assert_list(&vec![0, 1, 2, 3]);
+        let mut helper = helper_with_list(&[]);
+        let mut expected = vec![];
+        for value in 0..20 {
+            helper.push_front(value);
+            expected.insert(0, value);
+            if value % 5 == 4 {
+                helper.execute_expect_success(false);
+            }
+        }
+        helper.assert_list(&expected);
+    }
+
+    #[test]
+    fn test_get() {
+        let values: Vec<i32> = (0..30).map(|i| i * 10).collect();
+        let mut helper = helper_with_list(&values);
+        for index in [0, 1, 7, 15, 29] {
+            helper.get_success(index, Some(values[index as usize]));
+        }
+        helper.get_success(30, None);
+        helper.get_success(u64::MAX, None);
+    }
+
+    #[test]
+    fn test_insert_at_middle() {
+        let mut expected: Vec<i32> = (0..10).collect();
+        let mut helper = helper_with_list(&expected);
+        // Repeated inserts at the same position rotate the tree in the middle.
+        for value in 100..115 {
+            helper.insert_at(5, value);
+            expected.insert(5, value);
+            if value % 5 == 4 {
+                helper.execute_expect_success(false);
+            }
+        }
+        helper.assert_list(&expected);
+        for (index, value) in [(0, -1), (26, 200), (13, 300), (1, 400)] {
+            helper.insert_at(index, value);
+            expected.insert(index as usize, value);
+        }
+        helper.execute_expect_success(false);
+        helper.assert_list(&expected);
+    }
+
+    #[test]
+    fn test_insert_at_out_of_bounds_fails() {
+        let mut helper = helper_with_list(&[1, 2, 3]);
+        helper.insert_at(4, 0);
+        helper.execute_expect_failure(false);
+        helper.insert_at(3, 4);
+        helper.execute_expect_success(false);
+        helper.assert_list(&vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_remove_at() {
+        let mut expected: Vec<i32> = (0..30).collect();
+        let mut helper = helper_with_list(&expected);
+        for index in [0, 28, 10, 10, 10, 5, 20, 0] {
+            helper.remove_at_success(index, Some(expected.remove(index as usize)));
+        }
+        helper.remove_at_success(expected.len() as u64, None);
+        helper.assert_list(&expected);
+    }
+
+    #[test]
+    fn test_mixed_operations() {
+        let mut expected: Vec<i32> = vec![];
+        let mut helper = helper_with_list(&[]);
+        for round in 0..6 {
+            for step in 0..8 {
+                let value = round * 100 + step;
+                let index = ((value * 7) as usize) % (expected.len() + 1);
+                helper.insert_at(index as u64, value);
+                expected.insert(index, value);
+            }
+            for step in 0..3 {
+                let index = ((round * 13 + step * 5) as usize) % expected.len();
+                helper.remove_at(index as u64);
+                expected.remove(index);
+            }
+            helper.execute_expect_success(false);
+            helper.assert_list(&expected);
+        }
+        helper.update_value_success(3, 1000, Some(expected[3]));
+        expected[3] = 1000;
+        helper.assert_list(&expected);
+    }
+}
@@ -0,0 +1,61 @@
+mod helper_avl_tree_set;
+
+#[cfg(test)]
+mod avltree_set {
+    use super::*;
+    use helper_avl_tree_set::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_insert_contains_remove() {
+        let mut helper = helper_with_keys(&[]);
+        helper.insert_success(5, true);
+        helper.insert_success(5, false);
+        helper.contains_success(5, true);
+        helper.contains_success(6, false);
+        helper.remove_success(6, false);
+        helper.remove_success(5, true);
+        helper.remove_success(5, false);
+        helper.contains_success(5, false);
+    }
+
+    #[test]
+    fn test_range() {
+        let keys: Vec<i32> = (0..30).map(|i| (i * 11) % 30 * 2).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.get_range_success(10, 20, vec![10, 12, 14, 16, 18]);
+        helper.get_range_back_success(11, 21, vec![20, 18, 16, 14, 12]);
+        helper.get_range_success(57, 100, vec![58]);
+        helper.get_range_success(100, 200, vec![]);
+    }
+
+    #[test]
+    fn test_first_last() {
+        let mut helper = helper_with_keys(&[]);
+        helper.first_last_success((None, None));
+        helper.insert(7);
+        helper.execute_expect_success(false);
+        helper.first_last_success((Some(7), Some(7)));
+        let mut helper = helper_with_keys(&[4, -3, 9, 12, 0, 5]);
+        helper.first_last_success((Some(-3), Some(12)));
+        helper.remove(-3);
+        helper.remove(12);
+        helper.execute_expect_success(false);
+        helper.first_last_success((Some(0), Some(9)));
+    }
+
+    #[test]
+    fn test_floor_ceiling() {
+        let keys: Vec<i32> = (0..20).map(|i| i * 5).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.floor_ceiling_success(-1, (None, Some(0)));
+        helper.floor_ceiling_success(0, (Some(0), Some(0)));
+        helper.floor_ceiling_success(33, (Some(30), Some(35)));
+        helper.floor_ceiling_success(35, (Some(35), Some(35)));
+        helper.floor_ceiling_success(95, (Some(95), Some(95)));
+        helper.floor_ceiling_success(96, (Some(95), None));
+        helper.remove(35);
+        helper.execute_expect_success(false);
+        helper.floor_ceiling_success(35, (Some(30), Some(40)));
+    }
+}
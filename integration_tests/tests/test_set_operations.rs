@@ -0,0 +1,64 @@
+mod helper_avl_tree_set;
+
+#[cfg(test)]
+mod avltree_set_operations {
+    use super::*;
+    use helper_avl_tree_set::*;
+    use scrypto_testenv::TestHelperExecution;
+    use std::collections::BTreeSet;
+
+    fn assert_set_operations(left: &[i32], right: &[i32]) {
+        let mut helper = TestHelper::new();
+        helper.instantiate_default(false);
+        for chunk in left.chunks(10) {
+            for key in chunk.iter() {
+                helper.insert(*key);
+            }
+            helper.execute_expect_success(false);
+        }
+        for chunk in right.chunks(10) {
+            for key in chunk.iter() {
+                helper.insert_other(*key);
+            }
+            helper.execute_expect_success(false);
+        }
+
+        let left: BTreeSet<i32> = left.iter().cloned().collect();
+        let right: BTreeSet<i32> = right.iter().cloned().collect();
+        helper.union_success(left.union(&right).cloned().collect());
+        helper.intersection_success(left.intersection(&right).cloned().collect());
+        helper.difference_success(left.difference(&right).cloned().collect());
+        helper.symmetric_difference_success(left.symmetric_difference(&right).cloned().collect());
+    }
+
+    #[test]
+    fn test_set_operations_empty() {
+        assert_set_operations(&[], &[]);
+        assert_set_operations(&[1, 2, 3], &[]);
+        assert_set_operations(&[], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_set_operations_overlapping() {
+        assert_set_operations(&[1, 3, 5, 7, 9, 11], &[3, 4, 5, 6, 12]);
+        assert_set_operations(&[3, 4, 5, 6, 12], &[1, 3, 5, 7, 9, 11]);
+    }
+
+    #[test]
+    fn test_set_operations_equal_and_disjoint() {
+        let keys: Vec<i32> = (0..15).collect();
+        assert_set_operations(&keys, &keys);
+        let even: Vec<i32> = (0..15).map(|i| i * 2).collect();
+        let odd: Vec<i32> = (0..15).map(|i| i * 2 + 1).collect();
+        assert_set_operations(&even, &odd);
+    }
+
+    #[test]
+    fn test_set_operations_with_long_gaps() {
+        let dense: Vec<i32> = (0..40).map(|i| (i * 13) % 40).collect();
+        assert_set_operations(&dense, &[-5, 3, 17, 18, 39, 50]);
+        assert_set_operations(&[-5, 3, 17, 18, 39, 50], &dense);
+        assert_set_operations(&dense, &[100, 200]);
+        assert_set_operations(&[-100, 100], &dense);
+    }
+}
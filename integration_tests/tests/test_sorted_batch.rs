@@ -0,0 +1,76 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_sorted_batch {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn insert_batches(keys: &[i32], batches: &[Vec<i32>]) {
+        let mut helper = helper_with_keys(keys);
+        let mut expected = keys.to_vec();
+        for batch in batches {
+            helper.insert_sorted_batch(batch.clone());
+            helper.check_health();
+            helper.execute_expect_success(false);
+            expected.extend(
+                batch
+                    .iter()
+                    .filter(|key| !expected.contains(key))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        expected.sort();
+        let expected = expected.iter().map(|key| (*key, *key * 10)).collect();
+        helper.get_range_success(i32::MIN, i32::MAX, expected, false);
+    }
+
+    #[test]
+    fn test_insert_sorted_batch_into_tree() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        insert_batches(
+            &keys,
+            &[
+                vec![-3, 1, 2, 3, 41, 42, 95, 96, 97],
+                vec![150, 151, 188, 189, 300, 301, 302],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_insert_sorted_batch_into_empty_tree() {
+        insert_batches(
+            &[],
+            &[(0..15).collect(), (15..30).map(|key| key * 2).collect()],
+        );
+    }
+
+    #[test]
+    fn test_insert_sorted_batch_replaces_existing() {
+        let mut helper = helper_with_keys(&[10, 20, 30]);
+        helper.insert(20, 0);
+        helper.execute_expect_success(false);
+        helper.insert_sorted_batch(vec![5, 20, 25, 30]);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(
+            i32::MIN,
+            i32::MAX,
+            vec![(5, 50), (10, 100), (20, 200), (25, 250), (30, 300)],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_insert_unsorted_batch_fails() {
+        let mut helper = helper_with_keys(&[10, 20, 30]);
+        helper.insert_sorted_batch(vec![1, 15, 12]);
+        helper.execute_expect_failure(false);
+        helper.get_range_success(
+            i32::MIN,
+            i32::MAX,
+            vec![(10, 100), (20, 200), (30, 300)],
+            false,
+        );
+    }
+}
@@ -0,0 +1,107 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_split_off {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    fn key_values<I: IntoIterator<Item = i32>>(keys: I) -> Vec<(i32, i32)> {
+        keys.into_iter().map(|key| (key, key * 10)).collect()
+    }
+
+    fn check_trees(
+        helper: &mut TestHelper,
+        index: u64,
+        tree: Vec<(i32, i32)>,
+        split: Vec<(i32, i32)>,
+    ) {
+        helper.check_health();
+        helper.check_split_health(index);
+        helper.execute_expect_success(false);
+        let mut back = tree.clone();
+        back.reverse();
+        helper.get_range_success(i32::MIN, i32::MAX, tree, false);
+        helper.get_range_back_success(i32::MIN, i32::MAX, back, false);
+        helper.get_split_range_success(index, split);
+    }
+
+    #[test]
+    fn test_split_off_and_append() {
+        let mut helper = helper_with_keys(&(0..30).collect::<Vec<i32>>());
+        helper.split_off_success(12, 0);
+        check_trees(&mut helper, 0, key_values(0..12), key_values(12..30));
+        helper.append_split(0);
+        helper.execute_expect_success(false);
+        check_trees(&mut helper, 0, key_values(0..30), vec![]);
+    }
+
+    #[test]
+    fn test_split_off_between_keys() {
+        let keys: Vec<i32> = (0..20).map(|key| key * 10).collect();
+        let mut helper = helper_with_keys(&keys);
+        helper.split_off_success(55, 0);
+        check_trees(
+            &mut helper,
+            0,
+            key_values((0..6).map(|key| key * 10)),
+            key_values((6..20).map(|key| key * 10)),
+        );
+        helper.split_off_success(1000, 1);
+        check_trees(
+            &mut helper,
+            1,
+            key_values((0..6).map(|key| key * 10)),
+            vec![],
+        );
+        helper.split_off_success(-5, 2);
+        check_trees(
+            &mut helper,
+            2,
+            vec![],
+            key_values((0..6).map(|key| key * 10)),
+        );
+    }
+
+    #[test]
+    fn test_append_smaller_tree() {
+        let mut helper = helper_with_keys(&(0..30).collect::<Vec<i32>>());
+        helper.split_off_success(5, 0);
+        helper.execute_expect_success(false);
+        // The split tree with the bigger keys gets the smaller keys of the tree.
+        helper.append_to_split(0);
+        helper.execute_expect_success(false);
+        check_trees(&mut helper, 0, vec![], key_values(0..30));
+        helper.split_off_success(0, 1);
+        helper.append_split(0);
+        helper.execute_expect_success(false);
+        check_trees(&mut helper, 0, key_values(0..30), vec![]);
+    }
+
+    #[test]
+    fn test_append_unbalanced_trees() {
+        let mut helper = helper_with_keys(&(0..40).collect::<Vec<i32>>());
+        helper.split_off_success(38, 0);
+        helper.split_off_success(1, 1);
+        check_trees(&mut helper, 1, key_values(0..1), key_values(1..38));
+        helper.append_split(0);
+        helper.execute_expect_success(false);
+        check_trees(&mut helper, 0, key_values((0..1).chain(38..40)), vec![]);
+    }
+
+    #[test]
+    fn test_append_overlapping_fails() {
+        let mut helper = helper_with_keys(&(0..30).collect::<Vec<i32>>());
+        helper.split_off_success(10, 0);
+        helper.insert(40, 400);
+        helper.execute_expect_success(false);
+        helper.append_split(0);
+        helper.execute_expect_failure(false);
+        check_trees(
+            &mut helper,
+            0,
+            key_values((0..10).chain(40..41)),
+            key_values(10..30),
+        );
+    }
+}
@@ -0,0 +1,74 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_try {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto_avltree::avl_tree_error::AvlTreeError;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_try_insert() {
+        let mut helper = helper_with_keys(&[]);
+        for key in [5, 3, 8, 1, 4, 7, 9, 2, 6] {
+            helper.try_insert_success(key, key * 10, Ok(None));
+        }
+        helper.try_insert_success(4, 41, Ok(Some(40)));
+        helper.check_health();
+        helper.execute_expect_success(false);
+        helper.get_range_success(
+            0,
+            10,
+            vec![
+                (1, 10),
+                (2, 20),
+                (3, 30),
+                (4, 41),
+                (5, 50),
+                (6, 60),
+                (7, 70),
+                (8, 80),
+                (9, 90),
+            ],
+            false,
+        );
+    }
+
+    #[test]
+    fn test_try_remove() {
+        let keys: Vec<i32> = (0..20).collect();
+        let mut helper = helper_with_keys(&keys);
+        for key in [7, 0, 19, 10, 3] {
+            helper.try_remove_success(key, Ok(Some(key * 10)));
+        }
+        helper.try_remove_success(7, Ok(None));
+        helper.check_health();
+        helper.execute_expect_success(false);
+        let expected = keys
+            .iter()
+            .filter(|k| ![7, 0, 19, 10, 3].contains(*k))
+            .map(|k| (*k, *k * 10))
+            .collect();
+        helper.get_range_success(0, 20, expected, false);
+    }
+
+    #[test]
+    fn test_try_update_value() {
+        let mut helper = helper_with_keys(&[1, 2, 3]);
+        helper.try_update_value_success(2, 25, Ok(20));
+        helper.try_update_value_success(4, 40, Err(AvlTreeError::KeyNotFound { key: 4 }));
+        helper.get_range_success(0, 5, vec![(1, 10), (2, 25), (3, 30)], false);
+    }
+
+    #[test]
+    fn test_try_get_range() {
+        let keys: Vec<i32> = (0..15).map(|i| i * 2).collect();
+        let mut helper = helper_with_keys(&keys);
+        let expected: Vec<(i32, i32)> = (3..20)
+            .filter(|k| k % 2 == 0)
+            .map(|k| (k, k * 10))
+            .collect();
+        helper.try_get_range_success(3, 20, Ok(expected));
+        helper.try_get_range_success(30, 40, Ok(vec![]));
+    }
+}
@@ -0,0 +1,52 @@
+mod helper_avl_tree;
+
+#[cfg(test)]
+mod avltree_with_store {
+    use super::*;
+    use helper_avl_tree::*;
+    use scrypto::prelude::*;
+    use scrypto_testenv::TestHelperExecution;
+
+    #[test]
+    fn test_tree_with_store() {
+        let mut helper = TestHelper::new();
+        helper.instantiate_with_store_default(false);
+        let keys: Vec<i32> = (0..20).map(|index| (index * 7) % 20).collect();
+        for chunk in keys.chunks(10) {
+            for key in chunk.iter() {
+                helper.insert(*key, *key * 10);
+            }
+            helper.execute_expect_success(false);
+        }
+        helper.remove(7);
+        helper.check_health();
+        helper.execute_expect_success(false);
+        let expected: Vec<(i32, i32)> = (0..20)
+            .filter(|key| *key != 7)
+            .map(|key| (key, key * 10))
+            .collect();
+        helper.get_range_success(i32::MIN, i32::MAX, expected, false);
+    }
+
+    #[test]
+    fn test_store_address() {
+        let mut helper = TestHelper::new();
+        helper.instantiate_with_store_default(false);
+        let address = helper.store_address_success();
+        assert_eq!(
+            address.as_node_id().entity_type(),
+            Some(EntityType::InternalKeyValueStore)
+        );
+        helper.insert(1, 10);
+        helper.execute_expect_success(false);
+        assert_eq!(helper.store_address_success(), address);
+
+        helper.instantiate_default(false);
+        let other_address = helper.store_address_success();
+        assert_eq!(
+            other_address.as_node_id().entity_type(),
+            Some(EntityType::InternalKeyValueStore)
+        );
+        assert_ne!(other_address, address);
+    }
+}
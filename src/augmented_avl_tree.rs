@@ -0,0 +1,379 @@
+use scrypto::prelude::*;
+use std::hash::Hash;
+use std::ops::{Bound, Deref, RangeBounds, Sub};
+
+use crate::avl_tree::{AvlTree, Direction, ItemRef, Node, WithinBound};
+use crate::avl_tree_error::OrPanic;
+
+/// Summary of the entries of a subtree, e.g. a sum, a minimum or a maximum.
+///
+/// `combine` has to be associative and `empty` has to be its neutral element,
+/// because the summaries are combined in key order but in varying groupings.
+pub trait Aggregate<K, V>: ScryptoSbor + Clone {
+    /// The summary of no entries.
+    fn empty() -> Self;
+    /// The summary of a single entry.
+    fn from_entry(key: &K, value: &V) -> Self;
+    /// The summary of the entries of `self` followed by the entries of `right`.
+    fn combine(&self, right: &Self) -> Self;
+}
+
+/// An `AugmentedAvlTree` is an `AvlTree` where every node additionally stores the aggregate of its subtree.
+/// This allows to get the aggregate of any range in `O(log n)` instead of iterating over the range.
+///
+/// The aggregates are recomputed while the cache of the tree is written back to the KVStore.
+/// All nodes changed by the insert, the delete rewiring or a rotation are in the cache, so
+/// only these nodes and their ancestors are recomputed, children before their parents.
+#[derive(ScryptoSbor)]
+pub struct AugmentedAvlTree<K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor, A: ScryptoSbor> {
+    tree: AvlTree<K, AugmentedValue<V, A>>,
+}
+
+/// The value stored in the inner tree, the value of the user with the aggregate of the subtree.
+#[derive(ScryptoSbor, Clone)]
+pub(crate) struct AugmentedValue<V: ScryptoSbor, A: ScryptoSbor> {
+    pub(crate) value: V,
+    pub(crate) aggregate: A,
+}
+
+impl<
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        A: Aggregate<K, V>,
+    > Default for AugmentedAvlTree<K, V, A>
+{
+    fn default() -> Self {
+        AugmentedAvlTree::new()
+    }
+}
+
+impl<
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        A: Aggregate<K, V>,
+    > AugmentedAvlTree<K, V, A>
+{
+    /// Creates an empty `AugmentedAvlTree`.
+    pub fn new() -> Self {
+        AugmentedAvlTree {
+            tree: AvlTree::new(),
+        }
+    }
+
+    /// Returns the value of the given key in a AugmentedItemRef.
+    pub fn get(&self, key: &K) -> Option<AugmentedItemRef<'_, K, V, A>> {
+        self.tree.get(key).map(|item| AugmentedItemRef { item })
+    }
+
+    /// Inserts a new key value pair into the tree and updates the aggregates of all changed subtrees.
+    /// If the key already exists the old value is returned and the new value is inserted.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let augmented_value = AugmentedValue {
+            value,
+            aggregate: A::empty(),
+        };
+        let old_value = self
+            .tree
+            .insert_without_flush(key.clone(), augmented_value)
+            .or_panic();
+        // An existing value is replaced without caching the node, cache it to recompute its aggregate.
+        self.tree.get_node(&key);
+        self.flush();
+        old_value.map(|old| old.value)
+    }
+
+    /// Deletes the given key from the tree and updates the aggregates of all changed subtrees.
+    /// Returns the value of the deleted key if it existed.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.tree.remove_without_flush(key).or_panic();
+        self.flush();
+        value.map(|removed| removed.value)
+    }
+
+    /// Changes the value of the given key with the function and updates the aggregates afterwards.
+    /// Returns the result of the function or None if the key does not exist.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert(1, 10);
+    /// tree.update(&1, |value| *value += 5);
+    /// assert_eq!(tree.aggregate(..), Sum(15));
+    /// ```
+    pub fn update<R>(&mut self, key: &K, function: impl FnOnce(&mut V) -> R) -> Option<R> {
+        let result = {
+            let mut item = self.tree.get_mut(key)?;
+            function(&mut item.value)
+        };
+        self.tree.get_node(key);
+        self.flush();
+        Some(result)
+    }
+
+    /// Returns the aggregate of all entries of the tree in `O(1)`.
+    pub fn total(&self) -> A {
+        self.subtree_aggregate(self.tree.root.as_ref())
+    }
+
+    /// Returns the aggregate of all entries inside the range in `O(log n)`.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100 and value = key, aggregated by a sum.
+    /// ```
+    /// assert_eq!(tree.aggregate(10..15), Sum(60));
+    /// assert_eq!(tree.aggregate(..), Sum(4950));
+    /// ```
+    pub fn aggregate<R: RangeBounds<K>>(&self, range: R) -> A {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        // Descend to the first node inside the range, where the paths to the start and the end split.
+        let mut current = self.tree.root.clone();
+        while let Some(key) = current {
+            let node = self.node(&key);
+            if !start.within_bound(&key, Direction::Left) {
+                current = node.right_child.clone();
+            } else if !end.within_bound(&key, Direction::Right) {
+                current = node.left_child.clone();
+            } else {
+                let entry = A::from_entry(&key, &node.value.value);
+                let left = self.aggregate_from(start, node.left_child.clone());
+                let right = self.aggregate_until(end, node.right_child.clone());
+                return left.combine(&entry).combine(&right);
+            }
+        }
+        A::empty()
+    }
+
+    /// Finds the first key where the aggregate of all entries up to and including the key reaches the threshold,
+    /// e.g. the price level where the cumulative quantity of an order book reaches the order size.
+    /// The tree is descended once from the root, so the search needs `O(log n)`.
+    ///
+    /// The entries are accumulated in the given direction, `Right` starts at the smallest key and
+    /// `Left` at the biggest key.
+    /// The aggregate has to grow monotonically when entries are added, e.g. a sum of non-negative values.
+    /// Otherwise a prefix can reach the threshold inside a skipped subtree and the search returns a wrong key.
+    /// Returns None if the aggregate of the whole tree is below the threshold.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with the keys 1, 2, 3, 4 and value = 10, aggregated by a sum.
+    /// ```
+    /// let search = tree.find_by_prefix(Sum(25), Direction::Right).unwrap();
+    /// assert_eq!(search.key(), &3);
+    /// assert_eq!(search.before(), &Sum(20));
+    /// assert_eq!(search.remaining(), &Sum(5));
+    /// ```
+    pub fn find_by_prefix(&self, threshold: A, direction: Direction) -> Option<PrefixSearch<K, A>>
+    where
+        A: PartialOrd + Sub<Output = A>,
+    {
+        // Aggregate of all entries before the current subtree in the search direction.
+        let mut before = A::empty();
+        let mut current = self.tree.root.clone();
+        while let Some(key) = current {
+            let node = self.node(&key);
+            let (first_child, second_child) = match direction {
+                Direction::Right => (&node.left_child, &node.right_child),
+                Direction::Left => (&node.right_child, &node.left_child),
+            };
+            let first = self.subtree_aggregate(first_child.as_ref());
+            let with_first = combine_in_direction(&before, &first, direction);
+            if first_child.is_some() && with_first >= threshold {
+                current = first_child.clone();
+                continue;
+            }
+            let entry = A::from_entry(&key, &node.value.value);
+            let with_entry = combine_in_direction(&with_first, &entry, direction);
+            if with_entry >= threshold {
+                let remaining = threshold - with_first.clone();
+                return Some(PrefixSearch {
+                    key,
+                    before: with_first,
+                    remaining,
+                });
+            }
+            before = with_entry;
+            current = second_child.clone();
+        }
+        None
+    }
+
+    /// Iterates over the tree values in order of the keys, see `AvlTree::range`.
+    pub fn range<R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (K, V, Option<K>)> + '_ {
+        self.tree
+            .range(range)
+            .map(|(key, value, next_key)| (key, value.value, next_key))
+    }
+
+    /// Iterates backwards over the tree values, see `AvlTree::range_back`.
+    pub fn range_back<R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (K, V, Option<K>)> + '_ {
+        self.tree
+            .range_back(range)
+            .map(|(key, value, next_key)| (key, value.value, next_key))
+    }
+
+    // PRIVATE METHODS
+
+    /// Return the inner tree, public in crate for the health checking.
+    pub(crate) fn inner(&mut self) -> &mut AvlTree<K, AugmentedValue<V, A>> {
+        &mut self.tree
+    }
+
+    /// Inserts a new node next to the neighbour without comparing keys, see `AvlTree::insert_beside_without_flush`.
+    pub(crate) fn insert_beside(
+        &mut self,
+        neighbour: Option<&K>,
+        direction: Direction,
+        key: K,
+        value: V,
+    ) {
+        let augmented_value = AugmentedValue {
+            value,
+            aggregate: A::empty(),
+        };
+        self.tree
+            .insert_beside_without_flush(neighbour, direction, key, augmented_value)
+            .or_panic();
+        self.flush();
+    }
+
+    /// Writes the cache back to the KVStore and recomputes the aggregates of the changed subtrees.
+    fn flush(&mut self) {
+        let mut aggregates: HashMap<K, A> = HashMap::new();
+        for key in self.tree.cached_nodes_bottom_up() {
+            let node = self.tree.get_node(&key).expect("Cached node should exist");
+            let (left_child, right_child) = (node.left_child.clone(), node.right_child.clone());
+            let left = self.child_aggregate(left_child, &mut aggregates);
+            let right = self.child_aggregate(right_child, &mut aggregates);
+            let mut data = self.tree.flush_node(&key).expect("Node not found");
+            let aggregate = left
+                .combine(&A::from_entry(&key, &data.value.value))
+                .combine(&right);
+            data.value.aggregate = aggregate.clone();
+            aggregates.insert(key, aggregate);
+        }
+    }
+
+    /// Aggregate of a child, either recomputed in this flush or unchanged in the KVStore.
+    fn child_aggregate(&self, child: Option<K>, aggregates: &mut HashMap<K, A>) -> A {
+        match child {
+            Some(child) => aggregates
+                .remove(&child)
+                .unwrap_or_else(|| self.subtree_aggregate(Some(&child))),
+            None => A::empty(),
+        }
+    }
+
+    /// Stored aggregate of the subtree with the given root.
+    fn subtree_aggregate(&self, key: Option<&K>) -> A {
+        key.map_or_else(A::empty, |key| self.node(key).value.aggregate.clone())
+    }
+
+    /// Aggregate of all nodes of the subtree that are after the start bound.
+    /// The subtree has to be completely before the end bound.
+    fn aggregate_from(&self, start: Bound<&K>, mut current: Option<K>) -> A {
+        let mut result = A::empty();
+        while let Some(key) = current {
+            let node = self.node(&key);
+            if start.within_bound(&key, Direction::Left) {
+                let entry = A::from_entry(&key, &node.value.value);
+                let right = self.subtree_aggregate(node.right_child.as_ref());
+                result = entry.combine(&right).combine(&result);
+                current = node.left_child.clone();
+            } else {
+                current = node.right_child.clone();
+            }
+        }
+        result
+    }
+
+    /// Aggregate of all nodes of the subtree that are before the end bound.
+    /// The subtree has to be completely after the start bound.
+    fn aggregate_until(&self, end: Bound<&K>, mut current: Option<K>) -> A {
+        let mut result = A::empty();
+        while let Some(key) = current {
+            let node = self.node(&key);
+            if end.within_bound(&key, Direction::Right) {
+                let left = self.subtree_aggregate(node.left_child.as_ref());
+                let entry = A::from_entry(&key, &node.value.value);
+                result = result.combine(&left).combine(&entry);
+                current = node.right_child.clone();
+            } else {
+                current = node.left_child.clone();
+            }
+        }
+        result
+    }
+
+    /// Return the key of the root node, public in crate for the trees built on top.
+    pub(crate) fn root(&self) -> Option<K> {
+        self.tree.root.clone()
+    }
+
+    /// Return the node of the given key, public in crate for the trees built on top.
+    pub(crate) fn node(&self, key: &K) -> KeyValueEntryRef<'_, Node<K, AugmentedValue<V, A>>> {
+        self.tree
+            .store
+            .get(key)
+            .expect("Node of subtree should exist.")
+    }
+}
+
+/// Combines the aggregate of the entries before with the following aggregate in the given direction.
+fn combine_in_direction<K, V, A: Aggregate<K, V>>(before: &A, next: &A, direction: Direction) -> A {
+    match direction {
+        Direction::Right => before.combine(next),
+        Direction::Left => next.combine(before),
+    }
+}
+
+pub struct AugmentedItemRef<'a, K: ScryptoSbor, V: ScryptoSbor, A: ScryptoSbor> {
+    item: ItemRef<'a, K, AugmentedValue<V, A>>,
+}
+
+impl<'a, K: ScryptoSbor, V: ScryptoSbor, A: ScryptoSbor> Deref for AugmentedItemRef<'a, K, V, A> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item.value
+    }
+}
+
+impl<K: ScryptoSbor, V: ScryptoSbor, A: ScryptoSbor> AugmentedItemRef<'_, K, V, A> {
+    pub fn has_next(&self) -> bool {
+        self.item.has_next()
+    }
+    pub fn has_pref(&self) -> bool {
+        self.item.has_pref()
+    }
+}
+
+/// Result of `AugmentedAvlTree::find_by_prefix`.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq)]
+pub struct PrefixSearch<K: ScryptoSbor, A: ScryptoSbor> {
+    /// The key where the threshold is reached.
+    key: K,
+    /// The aggregate of all entries before the key.
+    before: A,
+    /// The part of the threshold that is left over for the entry of the key, `threshold - before`.
+    remaining: A,
+}
+
+impl<K: ScryptoSbor, A: ScryptoSbor> PrefixSearch<K, A> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn before(&self) -> &A {
+        &self.before
+    }
+
+    pub fn remaining(&self) -> &A {
+        &self.remaining
+    }
+}
@@ -0,0 +1,161 @@
+use scrypto::prelude::*;
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds};
+
+use crate::avl_tree::AvlTree;
+
+/// An `AvlMultiMap` is an `AvlTree` that allows multiple values for the same key, e.g. the orders at a price.
+/// The values of a key are kept in insertion order, the oldest value comes first.
+///
+/// Internally every entry is stored under the key combined with a sequence number,
+/// the sequence number is increased with every insert and never visible outside of the map.
+#[derive(ScryptoSbor)]
+pub struct AvlMultiMap<K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    tree: AvlTree<MultiKey<K>, V>,
+    sequence: u64,
+}
+
+/// The key of the inner tree, ordered by the key first and by the insertion order second.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct MultiKey<K: ScryptoSbor> {
+    pub(crate) key: K,
+    pub(crate) sequence: u64,
+}
+
+impl<K: ScryptoSbor + Display> Display for MultiKey<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.key, self.sequence)
+    }
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone> Default
+    for AvlMultiMap<K, V>
+{
+    fn default() -> Self {
+        AvlMultiMap::new()
+    }
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    AvlMultiMap<K, V>
+{
+    /// Creates an empty `AvlMultiMap`.
+    pub fn new() -> Self {
+        AvlMultiMap {
+            tree: AvlTree::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Adds the value to the key, behind all values that were inserted for the key before.
+    ///
+    /// Example:
+    /// ```
+    /// map.insert(1, "first");
+    /// map.insert(1, "second");
+    /// assert_eq!(map.get_all(&1).collect::<Vec<_>>(), vec!["first", "second"]);
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) {
+        let sequence = self.sequence;
+        self.sequence += 1;
+        self.tree.insert(MultiKey { key, sequence }, value);
+    }
+
+    /// Removes the oldest value of the key and returns it.
+    /// None is returned, if the key is not present in the map.
+    pub fn remove_one(&mut self, key: &K) -> Option<V> {
+        let first = self.tree.keys(Self::key_range(key)).next()?;
+        self.tree.remove(&first)
+    }
+
+    /// Removes all values of the key and returns them in insertion order.
+    pub fn remove_all(&mut self, key: &K) -> Vec<V> {
+        let keys: Vec<MultiKey<K>> = self.tree.keys(Self::key_range(key)).collect();
+        keys.iter()
+            .filter_map(|multi_key| self.tree.remove(multi_key))
+            .collect()
+    }
+
+    /// Iterates over the values of the key in insertion order.
+    pub fn get_all(&self, key: &K) -> impl DoubleEndedIterator<Item = V> + '_ {
+        self.tree
+            .range(Self::key_range(key))
+            .map(|(_, value, _)| value)
+    }
+
+    /// Returns `true` if the key has at least one value.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.tree.keys(Self::key_range(key)).next().is_some()
+    }
+
+    /// Iterates over the entries in order of the keys, the values of a key in insertion order.
+    /// The next key is the key of the next entry, which is the same key if the key has more values.
+    ///
+    /// Example:
+    /// ```
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// map.insert(2, "c");
+    /// for (k, v, next_key) in map.range(1..=2) {
+    ///     println!("{} {}", k, v);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 1 a, 2 b, 2 c
+    /// ```
+    pub fn range<R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (K, V, Option<K>)> + '_ {
+        self.tree
+            .range(Self::multi_key_range(range))
+            .map(|(multi_key, value, next)| (multi_key.key, value, next.map(|next| next.key)))
+    }
+
+    /// Iterates backwards over the entries, the values of a key in reversed insertion order.
+    pub fn range_back<R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (K, V, Option<K>)> + '_ {
+        self.tree
+            .range_back(Self::multi_key_range(range))
+            .map(|(multi_key, value, next)| (multi_key.key, value, next.map(|next| next.key)))
+    }
+
+    // PRIVATE METHODS
+
+    /// Return the inner tree, public in crate for the health checking.
+    pub(crate) fn inner(&mut self) -> &mut AvlTree<MultiKey<K>, V> {
+        &mut self.tree
+    }
+
+    /// The range of the inner keys that contains all values of the key.
+    fn key_range(key: &K) -> (Bound<MultiKey<K>>, Bound<MultiKey<K>>) {
+        Self::multi_key_range(key.clone()..=key.clone())
+    }
+
+    /// Converts a range of keys into the range of inner keys with all sequence numbers of the keys.
+    fn multi_key_range<R: RangeBounds<K>>(range: R) -> (Bound<MultiKey<K>>, Bound<MultiKey<K>>) {
+        let lowest = |key: &K| MultiKey {
+            key: key.clone(),
+            sequence: 0,
+        };
+        let highest = |key: &K| MultiKey {
+            key: key.clone(),
+            sequence: u64::MAX,
+        };
+        let start = match range.start_bound() {
+            Bound::Included(key) => Bound::Included(lowest(key)),
+            Bound::Excluded(key) => Bound::Excluded(highest(key)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => Bound::Included(highest(key)),
+            Bound::Excluded(key) => Bound::Excluded(lowest(key)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        (start, end)
+    }
+}
@@ -0,0 +1,138 @@
+use scrypto::prelude::*;
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds};
+
+use crate::avl_set_operations::SetOperationIterator;
+use crate::avl_tree::{AvlTree, KeyIterator};
+
+/// An `AvlSet` is an ordered set of keys, stored in an `AvlTree` without values.
+#[derive(ScryptoSbor)]
+pub struct AvlSet<K: ScryptoSbor + Eq + Ord + Hash> {
+    tree: AvlTree<K, ()>,
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug> Default for AvlSet<K> {
+    fn default() -> Self {
+        AvlSet::new()
+    }
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug> AvlSet<K> {
+    /// Creates an empty `AvlSet`.
+    pub fn new() -> Self {
+        AvlSet {
+            tree: AvlTree::new(),
+        }
+    }
+
+    /// Adds the key to the set.
+    /// Returns `true` if the key was not present in the set before.
+    pub fn insert(&mut self, key: K) -> bool {
+        self.tree.insert(key, ()).is_none()
+    }
+
+    /// Returns `true` if the key is present in the set.
+    pub fn contains(&self, key: &K) -> bool {
+        self.tree.get(key).is_some()
+    }
+
+    /// Removes the key from the set.
+    /// Returns `true` if the key was present in the set.
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.tree.remove(key).is_some()
+    }
+
+    /// Iterates over the keys of the range in ascending order.
+    ///
+    /// Example:
+    ///
+    /// Set is initialized with all even integers from 0 to 100.
+    /// ```
+    /// for k: K in set.range(10..20) {
+    ///     println!("{}", k);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 10, 12, 14, 16, 18
+    /// ```
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> KeyIterator<'_, K, ()> {
+        self.tree.keys(range)
+    }
+
+    /// Iterates over the keys of the range in descending order.
+    pub fn range_back<R: RangeBounds<K>>(&self, range: R) -> KeyIterator<'_, K, ()> {
+        self.tree.keys_back(range)
+    }
+
+    /// Returns the smallest key of the set.
+    pub fn first(&self) -> Option<K> {
+        self.tree.keys(..).next()
+    }
+
+    /// Returns the biggest key of the set.
+    pub fn last(&self) -> Option<K> {
+        self.tree.keys_back(..).next()
+    }
+
+    /// Returns the biggest key of the set that is smaller or equal to the given key.
+    ///
+    /// Example:
+    ///
+    /// Set is initialized with all even integers from 0 to 100.
+    /// ```
+    /// assert_eq!(set.floor(&15), Some(14));
+    /// assert_eq!(set.floor(&14), Some(14));
+    /// assert_eq!(set.floor(&-1), None);
+    /// ```
+    pub fn floor(&self, key: &K) -> Option<K> {
+        self.tree
+            .keys_back((Bound::Unbounded, Bound::Included(key.clone())))
+            .next()
+    }
+
+    /// Returns the smallest key of the set that is bigger or equal to the given key.
+    ///
+    /// Example:
+    ///
+    /// Set is initialized with all even integers from 0 to 100.
+    /// ```
+    /// assert_eq!(set.ceiling(&15), Some(16));
+    /// assert_eq!(set.ceiling(&16), Some(16));
+    /// assert_eq!(set.ceiling(&101), None);
+    /// ```
+    pub fn ceiling(&self, key: &K) -> Option<K> {
+        self.tree.keys(key.clone()..).next()
+    }
+
+    /// Iterates in ascending order over the keys that are in this or the other set, see `AvlTree::union`.
+    pub fn union<'a>(&'a self, other: &'a AvlSet<K>) -> SetOperationIterator<'a, K, (), ()> {
+        self.tree.union(&other.tree)
+    }
+
+    /// Iterates in ascending order over the keys that are in both sets, see `AvlTree::intersection`.
+    pub fn intersection<'a>(&'a self, other: &'a AvlSet<K>) -> SetOperationIterator<'a, K, (), ()> {
+        self.tree.intersection(&other.tree)
+    }
+
+    /// Iterates in ascending order over the keys that are in this set but not in the other set, see `AvlTree::difference`.
+    pub fn difference<'a>(&'a self, other: &'a AvlSet<K>) -> SetOperationIterator<'a, K, (), ()> {
+        self.tree.difference(&other.tree)
+    }
+
+    /// Iterates in ascending order over the keys that are in exactly one of the sets, see `AvlTree::symmetric_difference`.
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a AvlSet<K>,
+    ) -> SetOperationIterator<'a, K, (), ()> {
+        self.tree.symmetric_difference(&other.tree)
+    }
+
+    // PRIVATE METHODS
+
+    /// Return the inner tree, public in crate for the health checking.
+    pub(crate) fn inner(&mut self) -> &mut AvlTree<K, ()> {
+        &mut self.tree
+    }
+}
@@ -0,0 +1,214 @@
+use scrypto::prelude::*;
+use std::cmp::Ordering::{Equal, Greater, Less};
+use std::hash::Hash;
+
+use crate::avl_tree::AvlTree;
+
+/// The operation a `SetOperationIterator` applies to the keys of two trees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SetOperation {
+    /// Keys that are in at least one of the trees.
+    Union,
+    /// Keys that are in both trees.
+    Intersection,
+    /// Keys that are in the left tree but not in the right tree.
+    Difference,
+    /// Keys that are in exactly one of the trees.
+    SymmetricDifference,
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    AvlTree<K, V>
+{
+    /// Iterates in ascending order over the keys that are in this or the other tree.
+    ///
+    /// Example:
+    /// ```
+    /// // left = {1, 3, 5}, right = {3, 4}
+    /// assert_eq!(left.union(&right).collect::<Vec<_>>(), vec![1, 3, 4, 5]);
+    /// ```
+    pub fn union<'a, W: ScryptoSbor + Clone>(
+        &'a self,
+        other: &'a AvlTree<K, W>,
+    ) -> SetOperationIterator<'a, K, V, W> {
+        SetOperationIterator::new(self, other, SetOperation::Union)
+    }
+
+    /// Iterates in ascending order over the keys that are in this and the other tree.
+    /// Gaps of one tree are skipped with a search from the root, so the cost is bounded by the smaller tree.
+    ///
+    /// Example:
+    /// ```
+    /// // left = {1, 3, 5}, right = {3, 4}
+    /// assert_eq!(left.intersection(&right).collect::<Vec<_>>(), vec![3]);
+    /// ```
+    pub fn intersection<'a, W: ScryptoSbor + Clone>(
+        &'a self,
+        other: &'a AvlTree<K, W>,
+    ) -> SetOperationIterator<'a, K, V, W> {
+        SetOperationIterator::new(self, other, SetOperation::Intersection)
+    }
+
+    /// Iterates in ascending order over the keys that are in this tree but not in the other tree.
+    /// Gaps of the other tree are skipped with a search from the root, so the cost is bounded by this tree.
+    ///
+    /// Example:
+    /// ```
+    /// // left = {1, 3, 5}, right = {3, 4}
+    /// assert_eq!(left.difference(&right).collect::<Vec<_>>(), vec![1, 5]);
+    /// ```
+    pub fn difference<'a, W: ScryptoSbor + Clone>(
+        &'a self,
+        other: &'a AvlTree<K, W>,
+    ) -> SetOperationIterator<'a, K, V, W> {
+        SetOperationIterator::new(self, other, SetOperation::Difference)
+    }
+
+    /// Iterates in ascending order over the keys that are in exactly one of the trees.
+    ///
+    /// Example:
+    /// ```
+    /// // left = {1, 3, 5}, right = {3, 4}
+    /// assert_eq!(left.symmetric_difference(&right).collect::<Vec<_>>(), vec![1, 4, 5]);
+    /// ```
+    pub fn symmetric_difference<'a, W: ScryptoSbor + Clone>(
+        &'a self,
+        other: &'a AvlTree<K, W>,
+    ) -> SetOperationIterator<'a, K, V, W> {
+        SetOperationIterator::new(self, other, SetOperation::SymmetricDifference)
+    }
+}
+
+/// `SetOperationIterator` walks the linked lists of two trees in key order and yields the keys of their
+/// union, intersection, difference or symmetric difference.
+///
+/// A side that only has to catch up with the other side first tries the next node of its linked list in `O(1)`.
+/// If the next node is still behind, the gap is skipped by searching the first key that is not behind from the root in `O(log n)`.
+pub struct SetOperationIterator<
+    'a,
+    K: ScryptoSbor + Eq + Ord + Hash,
+    V: ScryptoSbor,
+    W: ScryptoSbor,
+> {
+    left_tree: &'a AvlTree<K, V>,
+    right_tree: &'a AvlTree<K, W>,
+    left: Option<K>,
+    right: Option<K>,
+    operation: SetOperation,
+}
+
+impl<
+        'a,
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        W: ScryptoSbor + Clone,
+    > SetOperationIterator<'a, K, V, W>
+{
+    fn new(
+        left_tree: &'a AvlTree<K, V>,
+        right_tree: &'a AvlTree<K, W>,
+        operation: SetOperation,
+    ) -> Self {
+        SetOperationIterator {
+            left: left_tree.keys(..).next(),
+            right: right_tree.keys(..).next(),
+            left_tree,
+            right_tree,
+            operation,
+        }
+    }
+
+    /// Returns the key after the given key in the tree.
+    fn step<T: ScryptoSbor + Clone>(tree: &AvlTree<K, T>, key: &K) -> Option<K> {
+        tree.store
+            .get(key)
+            .expect("Current key of the iterator should exist.")
+            .next
+            .clone()
+    }
+
+    /// Returns the first key of the tree that is bigger or equal to the target, starting after the given key.
+    fn seek<T: ScryptoSbor + Clone>(tree: &AvlTree<K, T>, key: &K, target: &K) -> Option<K> {
+        match Self::step(tree, key) {
+            Some(next) if next < *target => tree.keys(target.clone()..).next(),
+            next => next,
+        }
+    }
+
+    fn yields_left_only(&self) -> bool {
+        self.operation != SetOperation::Intersection
+    }
+
+    fn yields_right_only(&self) -> bool {
+        matches!(
+            self.operation,
+            SetOperation::Union | SetOperation::SymmetricDifference
+        )
+    }
+
+    fn yields_both(&self) -> bool {
+        matches!(
+            self.operation,
+            SetOperation::Union | SetOperation::Intersection
+        )
+    }
+}
+
+impl<
+        'a,
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        W: ScryptoSbor + Clone,
+    > Iterator for SetOperationIterator<'a, K, V, W>
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match (self.left.take(), self.right.take()) {
+                (None, None) => return None,
+                (Some(left), None) => {
+                    if !self.yields_left_only() {
+                        return None;
+                    }
+                    self.left = Self::step(self.left_tree, &left);
+                    return Some(left);
+                }
+                (None, Some(right)) => {
+                    if !self.yields_right_only() {
+                        return None;
+                    }
+                    self.right = Self::step(self.right_tree, &right);
+                    return Some(right);
+                }
+                (Some(left), Some(right)) => match left.cmp(&right) {
+                    Less => {
+                        if self.yields_left_only() {
+                            self.left = Self::step(self.left_tree, &left);
+                            self.right = Some(right);
+                            return Some(left);
+                        }
+                        self.left = Self::seek(self.left_tree, &left, &right);
+                        self.right = Some(right);
+                    }
+                    Greater => {
+                        if self.yields_right_only() {
+                            self.right = Self::step(self.right_tree, &right);
+                            self.left = Some(left);
+                            return Some(right);
+                        }
+                        self.right = Self::seek(self.right_tree, &right, &left);
+                        self.left = Some(left);
+                    }
+                    Equal => {
+                        self.left = Self::step(self.left_tree, &left);
+                        self.right = Self::step(self.right_tree, &right);
+                        if self.yields_both() {
+                            return Some(left);
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
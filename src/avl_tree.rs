@@ -1,3 +1,5 @@
+use scrypto::api::field_api::LockFlags;
+use scrypto::engine::scrypto_env::ScryptoVmV1Api;
 use scrypto::prelude::*;
 use std::cmp::Ordering;
 use std::cmp::Ordering::{Equal, Greater, Less};
@@ -5,6 +7,17 @@ use std::hash::Hash;
 use std::mem;
 use std::ops::{Bound, Deref, DerefMut, RangeBounds};
 
+use crate::avl_tree_error::{AvlTreeError, OrPanic};
+
+/// The parent of a changed subtree with the direction to it, and if the subtree got shorter.
+type ParentAndShortened<K> = (Option<(K, Direction)>, bool);
+/// A node borrowed from the KVStore.
+type StoredNode<'a, K, V> = KeyValueEntryRef<'a, Node<K, V>>;
+/// The root of a detached subtree with its height.
+pub(crate) type Subtree<K> = (Option<K>, i32);
+/// The first and the last key of a range that was cut out of the tree, with the detached subtree of the range.
+pub(crate) type CutRange<K> = (K, K, Subtree<K>);
+
 /// An `AvlTree` is a balanced binary tree.
 /// It is implemented as a double linked list with a binary tree on top.
 /// The double linked list is used to iterate over the tree in order.
@@ -18,7 +31,7 @@ pub struct AvlTree<K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
     /// The root of the tree.
     pub(crate) root: Option<K>,
     /// The store of the tree, the node stores the key, value, and navigation pointers in the tree, they are more explained in the Node struct.
-    store: KeyValueStore<K, Node<K, V>>,
+    pub(crate) store: KeyValueStore<K, Node<K, V>>,
     /// Cache the node information without the value.
     store_cache: HashMap<K, Node<K, ()>>,
 }
@@ -36,13 +49,37 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
 {
     /// Creates an empty `AvlTree`.
     pub fn new() -> Self {
+        AvlTree::with_store(KeyValueStore::new())
+    }
+
+    /// Creates an empty `AvlTree` on top of an existing KVStore, e.g. one created by the component itself.
+    /// The store has to be freshly created and empty, the tree does not check for nodes that are already in it.
+    ///
+    /// Example:
+    /// ```
+    /// let store: KeyValueStore<Decimal, Node<Decimal, Order>> = KeyValueStore::new();
+    /// let tree = AvlTree::with_store(store);
+    /// ```
+    pub fn with_store(store: KeyValueStore<K, Node<K, V>>) -> Self {
         AvlTree {
             root: None,
-            store: KeyValueStore::new(),
+            store,
             store_cache: HashMap::new(),
         }
     }
 
+    /// Returns the address of the KVStore that holds the nodes of the tree.
+    /// Gateways and indexers can use it to locate the nodes off-ledger and decode them as `Node<K, V>`.
+    /// A KVStore is always owned by a component, so its node id is always an internal address.
+    pub fn store_address(&self) -> InternalAddress {
+        InternalAddress::try_from(self.store_node_id()).expect("KVStore is always an internal node")
+    }
+
+    /// Returns the node id of the KVStore that holds the nodes of the tree.
+    pub fn store_node_id(&self) -> NodeId {
+        self.store.id.0
+    }
+
     /// Returns the value of the given key in a ItemRef.
     /// ```
     /// let tree = AvlTree::new();
@@ -69,6 +106,15 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         self.store.get_mut(key).map(|n| ItemRefMut { item: n })
     }
 
+    /// Returns the value of the given key in a mutable wrapper like `get_mut`,
+    /// but returns `AvlTreeError::KeyNotFound` if the key is not in the tree.
+    pub fn try_get_mut(&mut self, key: &K) -> Result<ItemRefMut<'_, K, V>, AvlTreeError<K>> {
+        self.store
+            .get_mut(key)
+            .map(|n| ItemRefMut { item: n })
+            .ok_or_else(|| AvlTreeError::KeyNotFound { key: key.clone() })
+    }
+
     /// Inserts a new key value pair into the tree.
     /// Operation needs in the worst case `2*(log(n)+1)` accesses to the KVStore.
     ///
@@ -85,13 +131,32 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     /// assert_eq!(*value, 2);
     /// ```
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        if let Some(mut existing_node) = self.store.get_mut(&key) {
-            return Some(mem::replace(&mut existing_node.value, value));
+        self.try_insert(key, value).or_panic()
+    }
+
+    /// Inserts a new key value pair into the tree like `insert`.
+    /// If the stored tree is inconsistent an `AvlTreeError` is returned instead of aborting the transaction,
+    /// the changes of the failed insert are discarded.
+    ///
+    /// Example:
+    /// ```
+    /// match tree.try_insert(1, 1) {
+    ///     Ok(old_value) => assert_eq!(old_value, None),
+    ///     Err(error) => panic!("Order book corrupted: {}", error),
+    /// }
+    /// ```
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, AvlTreeError<K>> {
+        let root = self.root.clone();
+        match self.insert_without_flush(key.clone(), value) {
+            Ok(old_value) => {
+                self.flush_cache()?;
+                Ok(old_value)
+            }
+            Err(error) => {
+                self.discard_changes(root, Some(&key));
+                Err(error)
+            }
         }
-        let parent = self.insert_node_in_empty_spot(&key, value);
-        self.balance_after_insert(parent);
-        self.flush_cache();
-        None
     }
 
     /// Deletes the given key from the tree.
@@ -107,13 +172,391 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     /// assert_eq!(value, None);
     /// ```
     pub fn remove(&mut self, key: &K) -> Option<V> {
-        if !self.contains_key(key) {
-            return None;
+        self.try_remove(key).or_panic()
+    }
+
+    /// Deletes the given key from the tree like `remove`.
+    /// If the stored tree is inconsistent an `AvlTreeError` is returned instead of aborting the transaction,
+    /// the changes of the failed delete are discarded.
+    /// The deleted node is only removed from the KVStore after the changed nodes are written back.
+    pub fn try_remove(&mut self, key: &K) -> Result<Option<V>, AvlTreeError<K>> {
+        let root = self.root.clone();
+        match self.unlink_without_flush(key) {
+            Ok(false) => Ok(None),
+            Ok(true) => {
+                self.flush_cache()?;
+                Ok(self.store.remove(key).map(|node| node.value))
+            }
+            Err(error) => {
+                self.discard_changes(root, None);
+                Err(error)
+            }
+        }
+    }
+
+    /// Inserts the key value pair only if the key is not in the tree yet.
+    /// If the key exists the tree is unchanged and the given value is returned in the error.
+    /// The key is looked up once in the KVStore, instead of a `get` followed by an `insert`.
+    ///
+    /// Example:
+    /// ```
+    /// assert_eq!(tree.insert_if_absent(1, 1), Ok(()));
+    /// assert_eq!(tree.insert_if_absent(1, 2), Err(2));
+    /// ```
+    pub fn insert_if_absent(&mut self, key: K, value: V) -> Result<(), V> {
+        if self.store.get(&key).is_some() {
+            return Err(value);
+        }
+        self.insert_absent_without_flush(key, value).or_panic();
+        self.flush_cache().or_panic();
+        Ok(())
+    }
+
+    /// Replaces the value of the key with `new` only if the current value equals `expected`.
+    /// Returns the replaced value on success, otherwise the current value or `None` if the key is not in the tree.
+    /// The key is looked up once in the KVStore and the entry is only written on a match,
+    /// so a failed compare does not write to the KVStore.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert(1, 10);
+    /// assert_eq!(tree.compare_and_swap(&1, &10, 11), Ok(10));
+    /// assert_eq!(tree.compare_and_swap(&1, &10, 12), Err(Some(11)));
+    /// assert_eq!(tree.compare_and_swap(&2, &10, 12), Err(None));
+    /// ```
+    pub fn compare_and_swap(&mut self, key: &K, expected: &V, new: V) -> Result<V, Option<V>>
+    where
+        V: PartialEq,
+    {
+        // A `KeyValueEntryRefMut` always writes the entry back when it is dropped,
+        // so the entry is opened directly to write it only after the compare.
+        let handle = ScryptoVmV1Api::kv_store_open_entry(
+            self.store.id.as_node_id(),
+            &scrypto_encode(key).unwrap(),
+            LockFlags::MUTABLE,
+        );
+        let stored: Option<Node<K, V>> =
+            scrypto_decode(&ScryptoVmV1Api::kv_entry_read(handle)).unwrap();
+        let result = match stored {
+            Some(mut node) if node.value == *expected => {
+                let old_value = mem::replace(&mut node.value, new);
+                ScryptoVmV1Api::kv_entry_write(handle, scrypto_encode(&node).unwrap());
+                Ok(old_value)
+            }
+            Some(node) => Err(Some(node.value)),
+            None => Err(None),
+        };
+        ScryptoVmV1Api::kv_entry_close(handle);
+        result
+    }
+
+    /// Updates the value of the key with the result of the closure, the key is removed if the closure returns `None`.
+    /// Returns the previous value, or `None` without calling the closure if the key is not in the tree.
+    /// The key is looked up once in the KVStore, a removal reuses the pointers of that lookup.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert(1, 10);
+    /// // Reduce the amount and remove the entry when it is used up.
+    /// tree.update(&1, |amount| (*amount > 4).then(|| amount - 4));
+    /// assert_eq!(*tree.get(&1).unwrap(), 6);
+    /// tree.update(&1, |amount| (*amount > 6).then(|| amount - 6));
+    /// assert!(tree.get(&1).is_none());
+    /// ```
+    pub fn update<F: FnOnce(&V) -> Option<V>>(&mut self, key: &K, f: F) -> Option<V> {
+        let mut node = self.store.get_mut(key)?;
+        match f(&node.value) {
+            Some(value) => Some(mem::replace(&mut node.value, value)),
+            None => {
+                let navigation = node.navigation();
+                drop(node);
+                self.store_cache.insert(key.clone(), navigation);
+                self.remove(key)
+            }
+        }
+    }
+
+    /// Moves the value of the `old` key to the `new` key without cloning it.
+    /// Returns `AvlTreeError::KeyNotFound` if `old` is not in the tree and `AvlTreeError::KeyExists` if `new` is,
+    /// in both cases the tree is unchanged.
+    ///
+    /// If `new` is between the previous and the next key of `old`, the node keeps its position in the tree
+    /// and only the pointers of its neighbours are renamed, without rebalancing.
+    /// Otherwise the node is removed and inserted again.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert(10, "order".to_string());
+    /// tree.rekey(&10, 12)?;
+    /// assert!(tree.get(&10).is_none());
+    /// assert_eq!(*tree.get(&12).unwrap(), "order");
+    /// ```
+    pub fn rekey(&mut self, old: &K, new: K) -> Result<(), AvlTreeError<K>> {
+        if self.store.get(&new).is_some() {
+            return Err(AvlTreeError::KeyExists { key: new });
+        }
+        if !self.contains_key(old) {
+            return Err(AvlTreeError::KeyNotFound { key: old.clone() });
+        }
+        let node = self.node(old).or_panic();
+        let keeps_position = node.prev.as_ref().map_or(true, |prev| *prev < new)
+            && node.next.as_ref().map_or(true, |next| new < *next);
+        if keeps_position {
+            self.rename_node(old, new).or_panic();
+        } else {
+            let value = self
+                .remove_without_flush(old)
+                .or_panic()
+                .expect("Node not found");
+            self.insert_absent_without_flush(new, value).or_panic();
+        }
+        self.flush_cache().or_panic();
+        Ok(())
+    }
+
+    /// Inserts a new key value pair like `insert`, but searches the position starting at the `hint` node.
+    /// The search climbs from the hint to the lowest common ancestor of the hint and the key and descends from there.
+    /// This reads fewer nodes than a search from the root if the ancestor is low in the tree, but it is still `O(log n)`:
+    /// if the hint and the key are on different sides of the root, the search climbs up to the root,
+    /// even if the keys are neighbours.
+    /// If the hint is not in the tree the search starts at the root.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert(100, 1);
+    /// tree.insert_with_hint(&100, 101, 2);
+    /// tree.insert_with_hint(&101, 102, 3);
+    /// ```
+    pub fn insert_with_hint(&mut self, hint: &K, key: K, value: V) -> Option<V> {
+        let old_value = self
+            .insert_with_hint_without_flush(Some(hint), key, value)
+            .or_panic();
+        self.flush_cache().or_panic();
+        old_value
+    }
+
+    /// Inserts a batch of entries that is sorted ascending by key, existing values are replaced like in `insert`.
+    /// Each key is searched from the previous key of the batch like in `insert_with_hint`
+    /// and the changed nodes are written back once after the whole batch.
+    /// So a batch of `m` keys reads roughly `O(m log(n/m))` nodes instead of `O(m log n)`.
+    ///
+    /// The function panics if the keys of the batch are not sorted ascending.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert_sorted_batch((100..110).map(|i| (i, i)));
+    /// ```
+    pub fn insert_sorted_batch<I: IntoIterator<Item = (K, V)>>(&mut self, entries: I) {
+        let mut previous: Option<K> = None;
+        for (key, value) in entries {
+            if let Some(previous) = previous.as_ref() {
+                assert!(
+                    *previous < key,
+                    "Keys of the batch have to be sorted ascending"
+                );
+            }
+            self.insert_with_hint_without_flush(previous.as_ref(), key.clone(), value)
+                .or_panic();
+            previous = Some(key);
+        }
+        self.flush_cache().or_panic();
+    }
+
+    /// Returns the entry of the key, or if it is not in the tree the entry with the next bigger key.
+    /// A key in the tree is read directly from the KVStore, otherwise the next key is searched starting at the `hint`
+    /// node like in `insert_with_hint`, which reads `O(log n)` nodes in the worst case.
+    ///
+    /// Example:
+    /// ```
+    /// let (key, value) = tree.get_near(&100, &105).unwrap();
+    /// info!("next order at {}: {}", key, *value);
+    /// ```
+    pub fn get_near(&self, hint: &K, key: &K) -> Option<(K, ItemRef<'_, K, V>)> {
+        if let Some(node) = self.store.get(key) {
+            return Some((key.clone(), ItemRef { item: node }));
+        }
+        let start = self.finger_start(hint, key).or_panic();
+        let next = self
+            .find_first_node(start, Bound::Included(key), Direction::Right)
+            .or_panic()?;
+        let node = self.store.get(&next)?;
+        Some((next, ItemRef { item: node }))
+    }
+
+    /// Appends a chunk of sorted entries to the end of the tree.
+    /// A big load can be split into multiple chunks, which are loaded in separate transactions.
+    /// The `state` is kept in the component between the chunks and the tree is valid after every chunk.
+    ///
+    /// Every key has to be bigger than all keys in the tree, otherwise the function panics.
+    /// Because every entry is appended at the end of the tree, the rebalancing is amortized `O(1)` per entry.
+    ///
+    /// Example:
+    /// ```
+    /// let mut state = BulkLoad::new();
+    /// tree.bulk_load(&mut state, (0..1000).map(|i| (i, i)));
+    /// // next transaction
+    /// tree.bulk_load(&mut state, (1000..2000).map(|i| (i, i)));
+    /// assert_eq!(state.loaded(), 2000);
+    /// ```
+    pub fn bulk_load<I: IntoIterator<Item = (K, V)>>(
+        &mut self,
+        state: &mut BulkLoad<K>,
+        entries: I,
+    ) {
+        let mut tail = self.resume_tail(state.tail.take());
+        for (key, value) in entries {
+            let parent = match tail {
+                Some(tail_key) => {
+                    assert!(
+                        key > tail_key,
+                        "Bulk load keys have to be sorted and bigger than all keys in the tree"
+                    );
+                    self.insert_node_and_adjust_pointers(
+                        &tail_key,
+                        &key,
+                        Some(value),
+                        Direction::Right,
+                    )
+                    .or_panic();
+                    Some((tail_key, Direction::Right))
+                }
+                None => {
+                    self.add_node(None, &key, Some(value), None, None);
+                    self.root = Some(key.clone());
+                    None
+                }
+            };
+            self.balance_after_insert(parent).or_panic();
+            tail = Some(key);
+            state.loaded += 1;
+        }
+        self.flush_cache().or_panic();
+        state.tail = tail;
+    }
+
+    /// Removes up to `limit` entries from the front of the tree.
+    /// Clearing a big tree can be split into multiple calls, which are executed in separate transactions.
+    /// The `state` is kept in the component between the calls and the tree is valid after every call.
+    ///
+    /// Returns `true` if the tree is empty afterwards.
+    ///
+    /// Example:
+    /// ```
+    /// let mut state = BulkDelete::new();
+    /// while !tree.bulk_delete(&mut state, 1000) {
+    ///     // next transaction
+    /// }
+    /// ```
+    pub fn bulk_delete(&mut self, state: &mut BulkDelete<K>, limit: usize) -> bool {
+        let mut head = self.resume_head(state.head.take());
+        for _ in 0..limit {
+            let Some(head_key) = head else {
+                break;
+            };
+            head = self.node(&head_key).or_panic().next.clone();
+            self.remove_without_flush(&head_key).or_panic();
+            state.removed += 1;
+        }
+        self.flush_cache().or_panic();
+        state.head = head;
+        self.root.is_none()
+    }
+
+    /// Removes all entries in the range and returns the number of removed entries.
+    /// The entries are cut out of the tree with two splits and one join, instead of rebalancing after every entry.
+    /// So the operation needs `O(log n)` accesses to the tree and one access per removed entry.
+    ///
+    /// Example:
+    /// ```
+    /// let removed = tree.remove_range(dec!(10)..dec!(20));
+    /// ```
+    pub fn remove_range<R: RangeBounds<K>>(&mut self, range: R) -> usize {
+        self.remove_range_internal(range, |_, _| {})
+    }
+
+    /// Removes all entries in the range like `remove_range` and returns them in ascending order.
+    ///
+    /// Example:
+    /// ```
+    /// for (key, value) in tree.drain_range(dec!(10)..dec!(20)) {
+    ///     info!("removed {}: {}", key, value);
+    /// }
+    /// ```
+    pub fn drain_range<R: RangeBounds<K>>(&mut self, range: R) -> Vec<(K, V)> {
+        let mut removed = Vec::new();
+        self.remove_range_internal(range, |key, value| removed.push((key, value)));
+        removed
+    }
+
+    /// Splits the tree at the given key and returns a new tree with all entries from the key on,
+    /// this tree keeps the smaller keys.
+    /// The tree is split in `O(log n)`, but every entry of the returned tree is moved to its own KVStore,
+    /// which costs two KVStore accesses per moved entry.
+    ///
+    /// Example:
+    /// ```
+    /// let upper = tree.split_off(&dec!(10));
+    /// assert!(tree.range(dec!(10)..).next().is_none());
+    /// ```
+    pub fn split_off(&mut self, key: &K) -> AvlTree<K, V> {
+        let mut other = AvlTree::new();
+        let Some(first) = self
+            .range_get_start(Bound::Included(key), Bound::Unbounded, Direction::Right)
+            .or_panic()
+        else {
+            return other;
+        };
+        let root = self.root.clone();
+        let height = self.height(root.clone()).or_panic();
+        let (left, right) = self
+            .split((root, height), &|node_key| match node_key < key {
+                true => Ordering::Less,
+                false => Ordering::Greater,
+            })
+            .or_panic();
+        self.root = left.0;
+        other.root = right.0;
+        // Cut the double linked list between the two trees.
+        let before = mem::take(&mut self.node_mut(&first).or_panic().prev);
+        if let Some(before) = before.as_ref() {
+            self.node_mut(before).or_panic().next = None;
+        }
+        let mut current = Some(first);
+        while let Some(key) = current {
+            let mut node = self.store.remove(&key).expect("Node not found");
+            if let Some(cached) = self.store_cache.remove(&key) {
+                node.set_navigation(&cached);
+            }
+            current = node.next.clone();
+            other.store.insert(key, node);
         }
-        let (start_tuple, shortened) = self.rewire_tree_for_delete(key);
-        self.balance_tree_after_delete(start_tuple, shortened);
-        self.flush_cache();
-        self.store.remove(&key).map(|n| n.value)
+        self.flush_cache().or_panic();
+        other
+    }
+
+    /// Moves all entries of the other tree into this tree, the other tree is empty afterwards.
+    /// The keys of the other tree have to be all bigger or all smaller than the keys of this tree,
+    /// otherwise the function panics.
+    /// The trees are joined in `O(log n)`, but every entry of the other tree is moved to the KVStore of this tree,
+    /// which costs two KVStore accesses per moved entry.
+    ///
+    /// Example:
+    /// ```
+    /// let mut upper = tree.split_off(&dec!(10));
+    /// tree.append(&mut upper);
+    /// ```
+    pub fn append(&mut self, other: &mut AvlTree<K, V>) {
+        let other_root = other.root.take();
+        let mut current = other
+            .find_first_node(other_root.clone(), Bound::Unbounded, Direction::Right)
+            .or_panic();
+        while let Some(key) = current {
+            let node = other.store.remove(&key).expect("Node not found");
+            current = node.next.clone();
+            self.store.insert(key, node);
+        }
+        self.append_without_flush(other_root).or_panic();
+        self.flush_cache().or_panic();
     }
 
     /// Iterate over the tree values in order of the keys.
@@ -149,6 +592,35 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         return self.range_internal(range.start_bound(), range.end_bound(), Direction::Right);
     }
 
+    /// Iterates over the tree values in order of the keys like `range`.
+    /// Instead of aborting the transaction, a missing node is returned as an `AvlTreeError`,
+    /// either by the search of the start or as the last item of the iteration.
+    ///
+    /// Example:
+    /// ```
+    /// for item in tree.try_range(10..20)? {
+    ///     let (k, v, next_key) = item?;
+    ///     println!("{}", k);
+    /// }
+    /// ```
+    pub fn try_range<R: RangeBounds<K>>(
+        &self,
+        range: R,
+    ) -> Result<TryNodeIterator<'_, K, V>, AvlTreeError<K>> {
+        let (start_bound, end_bound) = (range.start_bound(), range.end_bound());
+        let start = self.range_get_start(start_bound, end_bound, Direction::Right)?;
+        Ok(TryNodeIterator {
+            nodes: NodeIterator {
+                current: start,
+                back: None,
+                direction: Direction::Right,
+                start: start_bound.cloned(),
+                end: end_bound.cloned(),
+                tree: self,
+            },
+        })
+    }
+
     /// Iterates backwards over the tree values.
     ///
     /// Example:
@@ -225,41 +697,520 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         return self.range_mut_internal(range.end_bound(), range.start_bound(), Direction::Left);
     }
 
+    /// Continues the iteration of a `RangeCursor`, in the direction and with the end bound of the cursor.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100.
+    /// ```
+    /// let mut iter = tree.range(10..20);
+    /// iter.nth(4);
+    /// let cursor = iter.cursor().unwrap();
+    /// // next transaction
+    /// for (k: K, v: V, next_key: Option<K>) in tree.range_from_cursor(&cursor) {
+    ///     println!("{}", k);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 15, 16, 17, 18, 19
+    /// ```
+    pub fn range_from_cursor(&self, cursor: &RangeCursor<K>) -> NodeIterator<'_, K, V> {
+        self.range_internal(
+            cursor.from.as_bound(),
+            cursor.to.as_bound(),
+            cursor.direction,
+        )
+    }
+
+    /// Returns up to `limit` entries of the cursor's range and the cursor to continue with the next page.
+    /// The returned cursor is `None` if the range has no more entries.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100 and value = key.
+    /// ```
+    /// let (page, cursor) = tree.range_page(RangeCursor::new(10..20), 3);
+    /// assert_eq!(page, vec![(10, 10), (11, 11), (12, 12)]);
+    /// let (page, cursor) = tree.range_page(cursor.unwrap(), 10);
+    /// assert_eq!(page, vec![(13, 13), (14, 14), (15, 15), (16, 16), (17, 17), (18, 18), (19, 19)]);
+    /// assert_eq!(cursor, None);
+    /// ```
+    pub fn range_page(
+        &self,
+        cursor: RangeCursor<K>,
+        limit: usize,
+    ) -> (Vec<(K, V)>, Option<RangeCursor<K>>) {
+        let mut iter = self.range_from_cursor(&cursor);
+        let page = iter
+            .by_ref()
+            .take(limit)
+            .map(|(key, value, _)| (key, value))
+            .collect();
+        (page, iter.cursor())
+    }
+
+    /// Iterates over the tree like `range`, but yields the values as `ItemRef` instead of cloning them.
+    /// The value can be inspected in place and only the needed parts have to be cloned.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100 and value = vec![key; 100].
+    /// ```
+    /// for (k: K, v: ItemRef<K, V>) in tree.range_ref(10..15) {
+    ///     println!("{}: {}", k, v.len());
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 10: 100, 11: 100, 12: 100, 13: 100, 14: 100
+    /// ```
+    pub fn range_ref<R: RangeBounds<K>>(&self, range: R) -> NodeRefIterator<'_, K, V> {
+        NodeRefIterator {
+            nodes: self.range_internal(range.start_bound(), range.end_bound(), Direction::Right),
+        }
+    }
+
+    /// Iterates backwards over the tree like `range_back`, but yields the values as `ItemRef` instead of cloning them.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100 and value = vec![key; 100].
+    /// ```
+    /// for (k: K, v: ItemRef<K, V>) in tree.range_back_ref(10..15) {
+    ///     println!("{}: {}", k, v[0]);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 14: 14, 13: 13, 12: 12, 11: 11, 10: 10
+    /// ```
+    pub fn range_back_ref<R: RangeBounds<K>>(&self, range: R) -> NodeRefIterator<'_, K, V> {
+        NodeRefIterator {
+            nodes: self.range_internal(range.end_bound(), range.start_bound(), Direction::Left),
+        }
+    }
+
+    /// Iterates over the keys of the tree without cloning the values.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100.
+    /// ```
+    /// for k: K in tree.keys(10..15) {
+    ///     println!("{}", k);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 10, 11, 12, 13, 14
+    /// ```
+    pub fn keys<R: RangeBounds<K>>(&self, range: R) -> KeyIterator<'_, K, V> {
+        KeyIterator {
+            nodes: self.range_internal(range.start_bound(), range.end_bound(), Direction::Right),
+        }
+    }
+
+    /// Iterates backwards over the keys of the tree without cloning the values.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100.
+    /// ```
+    /// for k: K in tree.keys_back(10..15) {
+    ///     println!("{}", k);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 14, 13, 12, 11, 10
+    /// ```
+    pub fn keys_back<R: RangeBounds<K>>(&self, range: R) -> KeyIterator<'_, K, V> {
+        KeyIterator {
+            nodes: self.range_internal(range.end_bound(), range.start_bound(), Direction::Left),
+        }
+    }
+
+    /// Returns the first key for which the predicate on key and value is true.
+    /// The predicate has to be monotone, i.e. false for all entries before the partition point and true for all entries after it.
+    /// The search descends from the root in `O(log n)` without iterating over the entries.
+    ///
+    /// Returns `None` if the predicate is false for all entries.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100 and value = 10 * key.
+    /// ```
+    /// let key = tree.partition_point(|_, value| *value > 425);
+    /// assert_eq!(key, Some(43));
+    /// ```
+    pub fn partition_point<F: FnMut(&K, &V) -> bool>(&self, mut predicate: F) -> Option<K> {
+        let mut current = self.root.clone();
+        let mut result = None;
+        while let Some(key) = current {
+            let node = self.store.get(&key).expect("Node of subtree should exist.");
+            current = match predicate(&node.key, &node.value) {
+                true => {
+                    // Current node is a candidate -> search for an earlier one on the left.
+                    result = Some(key);
+                    node.left_child.clone()
+                }
+                false => node.right_child.clone(),
+            };
+        }
+        result
+    }
+
     // PRIVATE METHODS
 
     /// Return the internal representation of the tree, public in crate for the health checking.
     pub(crate) fn get_node(&mut self, key: &K) -> Option<&Node<K, ()>> {
         self.cache_if_missing(key);
         // Carefully this is not synced with the store!
-        self.store_cache.get(&key)
+        self.store_cache.get(key)
+    }
+
+    /// Return the cached node, `AvlTreeError::MissingNode` if a pointer references a key that is not stored.
+    fn node(&mut self, key: &K) -> Result<&Node<K, ()>, AvlTreeError<K>> {
+        self.cache_if_missing(key);
+        self.store_cache
+            .get(key)
+            .ok_or_else(|| AvlTreeError::MissingNode { key: key.clone() })
     }
 
-    /// Return the internal representation of the tree.
-    fn get_mut_node(&mut self, key: &K) -> Option<&mut Node<K, ()>> {
+    /// Return the mutable cached node, `AvlTreeError::MissingNode` if a pointer references a key that is not stored.
+    fn node_mut(&mut self, key: &K) -> Result<&mut Node<K, ()>, AvlTreeError<K>> {
         self.cache_if_missing(key);
-        self.store_cache.get_mut(key)
+        self.store_cache
+            .get_mut(key)
+            .ok_or_else(|| AvlTreeError::MissingNode { key: key.clone() })
     }
 
     /// Caches the node information from the radix KV store.
     fn cache_if_missing(&mut self, key: &K) {
-        if self.store_cache.contains_key(&key) {
+        if self.store_cache.contains_key(key) {
             return;
         }
-        self.store.get(&key).map(|data| {
-            self.store_cache.insert(
-                key.clone(),
-                Node {
-                    key: data.key.clone(),
-                    value: (),
-                    left_child: data.left_child.clone(),
-                    right_child: data.right_child.clone(),
-                    parent: data.parent.clone(),
-                    prev: data.prev.clone(),
-                    next: data.next.clone(),
-                    balance_factor: data.balance_factor,
-                },
-            )
-        });
+        self.store
+            .get(key)
+            .map(|data| self.store_cache.insert(key.clone(), data.navigation()));
+    }
+
+    /// Inserts the given key value pair without writing the cache back to the KVStore.
+    /// An existing value is replaced directly in the KVStore and not cached.
+    pub(crate) fn insert_without_flush(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<Option<V>, AvlTreeError<K>> {
+        if let Some(mut existing_node) = self.store.get_mut(&key) {
+            return Ok(Some(mem::replace(&mut existing_node.value, value)));
+        }
+        self.insert_absent_without_flush(key, value)?;
+        Ok(None)
+    }
+
+    /// Inserts the given key value pair like `insert_without_flush`, but searches the position starting at the hint.
+    fn insert_with_hint_without_flush(
+        &mut self,
+        hint: Option<&K>,
+        key: K,
+        value: V,
+    ) -> Result<Option<V>, AvlTreeError<K>> {
+        if let Some(mut existing_node) = self.store.get_mut(&key) {
+            return Ok(Some(mem::replace(&mut existing_node.value, value)));
+        }
+        let start = match hint {
+            Some(hint) => self.finger_start(hint, &key)?,
+            None => self.root.clone(),
+        };
+        let parent = self.insert_node_in_empty_spot(start, &key, Some(value))?;
+        self.balance_after_insert(parent)?;
+        Ok(None)
+    }
+
+    /// Inserts a key that is not in the tree yet without writing the cache back to the KVStore.
+    fn insert_absent_without_flush(&mut self, key: K, value: V) -> Result<(), AvlTreeError<K>> {
+        let parent = self.insert_node_in_empty_spot(self.root.clone(), &key, Some(value))?;
+        self.balance_after_insert(parent)?;
+        Ok(())
+    }
+
+    /// Inserts a new node next to the neighbour in the order of the tree without comparing any keys,
+    /// e.g. for trees that are ordered by position instead of by key.
+    /// The neighbour is only `None` if the tree is empty.
+    pub(crate) fn insert_beside_without_flush(
+        &mut self,
+        neighbour: Option<&K>,
+        direction: Direction,
+        key: K,
+        value: V,
+    ) -> Result<(), AvlTreeError<K>> {
+        let Some(neighbour) = neighbour else {
+            assert!(
+                self.root.is_none(),
+                "Neighbour is required in a non empty tree"
+            );
+            self.add_node(None, &key, Some(value), None, None);
+            self.root = Some(key);
+            return Ok(());
+        };
+        let neighbour_node = self.node(neighbour)?;
+        // The new node becomes the child of the neighbour, or if that spot is taken the child of the
+        // outermost node of that subtree, which is the next node in the direction.
+        let parent = match neighbour_node.get_child(direction) {
+            None => (neighbour.clone(), direction),
+            Some(_) => (
+                // A node with a child in the direction has a neighbour there.
+                neighbour_node.get_prev_next(direction).ok_or_else(|| {
+                    AvlTreeError::CorruptedPointer {
+                        key: neighbour.clone(),
+                    }
+                })?,
+                direction.opposite(),
+            ),
+        };
+        self.insert_node_and_adjust_pointers(&parent.0, &key, Some(value), parent.1)?;
+        self.balance_after_insert(Some(parent))?;
+        Ok(())
+    }
+
+    /// Deletes the given key from the tree without writing the cache back to the KVStore.
+    /// The deleted node is removed from the cache, so the cache can be shared by multiple operations.
+    pub(crate) fn remove_without_flush(&mut self, key: &K) -> Result<Option<V>, AvlTreeError<K>> {
+        if !self.unlink_without_flush(key)? {
+            return Ok(None);
+        }
+        Ok(self.store.remove(key).map(|n| n.value))
+    }
+
+    /// Joins the tree of `other_root`, whose nodes are already in the KVStore of this tree, into this tree
+    /// without writing the cache back to the KVStore. The cache has to be written back before,
+    /// because the other tree is searched in the KVStore.
+    /// The keys of the other tree have to be all bigger or all smaller than the keys of this tree,
+    /// otherwise the function panics.
+    pub(crate) fn append_without_flush(
+        &mut self,
+        other_root: Option<K>,
+    ) -> Result<(), AvlTreeError<K>> {
+        let (Some(other_first), Some(other_last)) = (
+            self.find_first_node(other_root.clone(), Bound::Unbounded, Direction::Right)?,
+            self.find_first_node(other_root.clone(), Bound::Unbounded, Direction::Left)?,
+        ) else {
+            return Ok(());
+        };
+        let first = self.find_first_node(self.root.clone(), Bound::Unbounded, Direction::Right)?;
+        let last = self.find_first_node(self.root.clone(), Bound::Unbounded, Direction::Left)?;
+        // The outermost entry of the other tree next to this tree becomes the middle node of the join.
+        let other_is_right = match (first.as_ref(), last.as_ref()) {
+            (Some(_), Some(last)) if *last < other_first => true,
+            (Some(first), Some(_)) if other_last < *first => false,
+            (None, None) => true,
+            _ => panic!("Key ranges of the trees overlap"),
+        };
+        let middle = match other_is_right {
+            true => other_first,
+            false => other_last,
+        };
+        // The middle node is taken out of the other tree, it keeps its entry in the KVStore.
+        // The other tree is written back, so its new border can be searched in the KVStore.
+        let root = mem::replace(&mut self.root, other_root);
+        self.unlink_without_flush(&middle)?;
+        self.flush_cache()?;
+        let other_root = mem::replace(&mut self.root, root);
+
+        let root = self.root.clone();
+        let this = (root.clone(), self.height(root)?);
+        let moved = (other_root.clone(), self.height(other_root)?);
+        let (prev, next, left, right) = match other_is_right {
+            true => {
+                let next =
+                    self.find_first_node(moved.0.clone(), Bound::Unbounded, Direction::Right)?;
+                (last, next, this, moved)
+            }
+            false => {
+                let prev =
+                    self.find_first_node(moved.0.clone(), Bound::Unbounded, Direction::Left)?;
+                (prev, first, moved, this)
+            }
+        };
+        if let Some(prev) = prev.as_ref() {
+            self.node_mut(prev)?.next = Some(middle.clone());
+        }
+        if let Some(next) = next.as_ref() {
+            self.node_mut(next)?.prev = Some(middle.clone());
+        }
+        self.add_node(None, &middle, None, prev, next);
+        self.root = Some(self.join(left, middle, right)?.0);
+        Ok(())
+    }
+
+    /// Adds a node that is already in the KVStore but in no tree to this tree, without writing the cache back.
+    /// It is used to move a node unlinked from another tree of the same KVStore, its value is neither read nor written.
+    pub(crate) fn link_without_flush(&mut self, key: K) -> Result<(), AvlTreeError<K>> {
+        let parent = self.insert_node_in_empty_spot(self.root.clone(), &key, None)?;
+        self.balance_after_insert(parent)?;
+        Ok(())
+    }
+
+    /// Takes the node of the key out of the tree and removes it from the cache, but keeps it in the KVStore.
+    /// Returns `false` if the key is not in the tree.
+    pub(crate) fn unlink_without_flush(&mut self, key: &K) -> Result<bool, AvlTreeError<K>> {
+        if !self.contains_key(key) {
+            return Ok(false);
+        }
+        let (start_tuple, shortened) = self.rewire_tree_for_delete(key)?;
+        self.balance_tree_after_delete(start_tuple, shortened)?;
+        self.store_cache.remove(key);
+        Ok(true)
+    }
+
+    /// Drops the cached changes of a failed operation, so the KVStore keeps the tree from before the operation.
+    /// The node that was already added to the KVStore by a failed insert is removed again,
+    /// a new node is always cached.
+    fn discard_changes(&mut self, root: Option<K>, added: Option<&K>) {
+        if let Some(key) = added.filter(|key| self.store_cache.contains_key(*key)) {
+            self.store.remove(key);
+        }
+        self.store_cache.clear();
+        self.root = root;
+    }
+
+    /// Returns the lowest common ancestor of the hint and the position of the key, to start a search there.
+    /// Climbing from the hint, the first ancestor beyond the key has the hint on one side and the key on the other side.
+    /// Its subtree contains the position of the key and all keys between the hint and the key.
+    /// Returns the root if the hint is not in the tree.
+    fn finger_start(&self, hint: &K, key: &K) -> Result<Option<K>, AvlTreeError<K>> {
+        // A hint equal to the key is not in the tree, the callers look up existing keys directly.
+        let (Some(direction), Some(mut parent)) = (
+            Direction::from_ordering(key.cmp(hint)),
+            self.parent_of(hint),
+        ) else {
+            return Ok(self.root.clone());
+        };
+        let mut current = hint.clone();
+        while let Some(parent_key) = parent {
+            if Direction::from_ordering(parent_key.cmp(key)) != Some(direction.opposite()) {
+                return Ok(Some(parent_key));
+            }
+            parent = self
+                .parent_of(&parent_key)
+                .ok_or_else(|| AvlTreeError::MissingNode {
+                    key: parent_key.clone(),
+                })?;
+            current = parent_key;
+        }
+        Ok(Some(current))
+    }
+
+    /// Returns the parent of the node, `None` if the node is not in the tree.
+    /// A cached node is not written back yet, so its parent is taken from the cache.
+    fn parent_of(&self, key: &K) -> Option<Option<K>> {
+        match self.store_cache.get(key) {
+            Some(node) => Some(node.parent.clone()),
+            None => self.store.get(key).map(|node| node.parent.clone()),
+        }
+    }
+
+    /// Cuts the entries of the range out of the tree and passes them to `removed` in ascending order.
+    fn remove_range_internal<R: RangeBounds<K>, F: FnMut(K, V)>(
+        &mut self,
+        range: R,
+        mut removed: F,
+    ) -> usize {
+        let Some((first, last, _)) = self.cut_range_without_flush(range).or_panic() else {
+            return 0;
+        };
+        let mut count = 0;
+        let mut current = Some(first);
+        while let Some(key) = current {
+            self.store_cache.remove(&key);
+            let node = self.store.remove(&key).expect("Node not found");
+            current = match key == last {
+                true => None,
+                false => node.next,
+            };
+            removed(key, node.value);
+            count += 1;
+        }
+        self.flush_cache().or_panic();
+        count
+    }
+
+    /// Cuts the entries of the range out of the tree without writing the cache back to the KVStore.
+    /// The entries stay in the KVStore as a detached tree, whose double linked list ends at its first and last entry.
+    /// Returns the first and the last key of the range and the root and height of the detached tree,
+    /// `None` if the range is empty.
+    /// The tree is split twice and joined once, so only `O(log n)` nodes are changed.
+    pub(crate) fn cut_range_without_flush<R: RangeBounds<K>>(
+        &mut self,
+        range: R,
+    ) -> Result<Option<CutRange<K>>, AvlTreeError<K>> {
+        let (start_bound, end_bound) = (range.start_bound(), range.end_bound());
+        let Some(first) = self.range_get_start(start_bound, end_bound, Direction::Right)? else {
+            return Ok(None);
+        };
+        let last = self
+            .range_get_start(end_bound, start_bound, Direction::Left)?
+            .ok_or_else(|| AvlTreeError::CorruptedPointer { key: first.clone() })?;
+        let before = mem::take(&mut self.node_mut(&first)?.prev);
+        let after = mem::take(&mut self.node_mut(&last)?.next);
+
+        // Split the tree at the node after the range, then split the range off the left part.
+        // The node after the range joins the remaining parts again.
+        let root = self.root.clone();
+        let height = self.height(root.clone())?;
+        let (rest, right) = match after.clone() {
+            Some(after) => {
+                let (left, right) = self.split((root, height), &|key| key.cmp(&after))?;
+                (left, Some((after, right)))
+            }
+            None => ((root, height), None),
+        };
+        let (left, cut) = self.split(rest, &|key| match key < &first {
+            true => Ordering::Less,
+            false => Ordering::Greater,
+        })?;
+        self.root = match right {
+            Some((after, right)) => Some(self.join(left, after, right)?.0),
+            None => left.0,
+        };
+
+        // Only the neighbours of the range are changed in the double linked list.
+        if let Some(before) = before.as_ref() {
+            self.node_mut(before)?.next = after.clone();
+        }
+        if let Some(after) = after.as_ref() {
+            self.node_mut(after)?.prev = before;
+        }
+        Ok(Some((first, last, cut)))
+    }
+
+    /// Returns the biggest key of the tree.
+    /// The tail saved in a bulk load state is used if it is still the last node, otherwise the tree is searched.
+    fn resume_tail(&mut self, saved_tail: Option<K>) -> Option<K> {
+        saved_tail
+            .filter(|tail| self.get_node(tail).is_some_and(|node| node.next.is_none()))
+            .or_else(|| {
+                self.find_first_node(self.root.clone(), Bound::Unbounded, Direction::Left)
+                    .or_panic()
+            })
+    }
+
+    /// Returns the smallest key of the tree.
+    /// The head saved in a bulk delete state is used if it is still the first node, otherwise the tree is searched.
+    fn resume_head(&mut self, saved_head: Option<K>) -> Option<K> {
+        saved_head
+            .filter(|head| self.get_node(head).is_some_and(|node| node.prev.is_none()))
+            .or_else(|| {
+                self.find_first_node(self.root.clone(), Bound::Unbounded, Direction::Right)
+                    .or_panic()
+            })
     }
 
     ///  Check if key is present in the tree.
@@ -268,18 +1219,89 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         self.store_cache.contains_key(key)
     }
 
+    /// Returns the direction from the child to its parent, `Right` if the child is the left child of the parent.
+    /// The direction is taken from the pointers of the parent and not by comparing the keys,
+    /// so the balancing also works for trees that are ordered by position instead of by key.
+    fn direction_to_parent(&mut self, parent: &K, child: &K) -> Result<Direction, AvlTreeError<K>> {
+        let parent_node = self.node(parent)?;
+        if parent_node.left_child.as_ref() == Some(child) {
+            Ok(Direction::Right)
+        } else if parent_node.right_child.as_ref() == Some(child) {
+            Ok(Direction::Left)
+        } else {
+            Err(AvlTreeError::CorruptedPointer { key: child.clone() })
+        }
+    }
+
     /// empties the cache and writes back the changes to the radix KV store.
-    fn flush_cache(&mut self) {
-        for (key, value) in self.store_cache.iter() {
-            let mut data = self.store.get_mut(key).expect("Node not found");
-            data.left_child = value.left_child.clone();
-            data.right_child = value.right_child.clone();
-            data.parent = value.parent.clone();
-            data.prev = value.prev.clone();
-            data.next = value.next.clone();
-            data.balance_factor = value.balance_factor.clone();
+    /// The cache is only filled with nodes that were read from or written to the KVStore,
+    /// so the `MissingNode` error is only returned if the KVStore was changed behind the cache.
+    pub(crate) fn flush_cache(&mut self) -> Result<(), AvlTreeError<K>> {
+        for (key, value) in self.store_cache.drain() {
+            let mut data = self
+                .store
+                .get_mut(&key)
+                .ok_or_else(|| AvlTreeError::MissingNode { key: key.clone() })?;
+            data.set_navigation(&value);
         }
-        self.store_cache.clear();
+        Ok(())
+    }
+
+    /// Writes the cached node back to the radix KV store and removes it from the cache.
+    /// Returns the stored node, so additional data can be written with the same access.
+    pub(crate) fn flush_node(&mut self, key: &K) -> Option<KeyValueEntryRefMut<'_, Node<K, V>>> {
+        let cached = self.store_cache.remove(key)?;
+        let mut data = self.store.get_mut(key).expect("Node not found");
+        data.set_navigation(&cached);
+        Some(data)
+    }
+
+    /// Returns the keys of all cached nodes and of all their ancestors, children before their parents.
+    /// The ancestors are added to the cache, so these are exactly the nodes whose subtree might have
+    /// changed since the last flush.
+    pub(crate) fn cached_nodes_bottom_up(&mut self) -> Vec<K> {
+        let cached_keys: Vec<K> = self.store_cache.keys().cloned().collect();
+        let mut visited: HashSet<K> = HashSet::new();
+        for key in cached_keys {
+            let mut current = Some(key);
+            while let Some(current_key) = current {
+                if !visited.insert(current_key.clone()) {
+                    break;
+                }
+                current = self
+                    .get_node(&current_key)
+                    .expect("Cached node should exist")
+                    .parent
+                    .clone();
+            }
+        }
+        let mut bottom_up = Vec::with_capacity(visited.len());
+        let mut stack: Vec<(K, bool)> = self
+            .root
+            .iter()
+            .filter(|root| visited.contains(*root))
+            .map(|root| (root.clone(), false))
+            .collect();
+        while let Some((key, children_pushed)) = stack.pop() {
+            if children_pushed {
+                bottom_up.push(key);
+                continue;
+            }
+            let node = self
+                .store_cache
+                .get(&key)
+                .expect("Cached node should exist");
+            let children = [node.left_child.clone(), node.right_child.clone()];
+            stack.push((key, true));
+            stack.extend(
+                children
+                    .into_iter()
+                    .flatten()
+                    .filter(|child| visited.contains(child))
+                    .map(|child| (child, false)),
+            );
+        }
+        bottom_up
     }
 
     /// Wrapper function for range and range_back.
@@ -289,12 +1311,16 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         end_bound: Bound<&K>,
         direction: Direction,
     ) -> NodeIterator<K, V> {
-        let start = self.range_get_start(start_bound, end_bound, direction);
+        let start = self
+            .range_get_start(start_bound, end_bound, direction)
+            .or_panic();
         NodeIterator {
             current: start,
+            back: None,
             direction,
+            start: start_bound.cloned(),
             end: end_bound.cloned(),
-            store: &self.store,
+            tree: self,
         }
     }
 
@@ -305,7 +1331,9 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         end_bound: Bound<&K>,
         direction: Direction,
     ) -> NodeIteratorMut<K, V> {
-        let start = self.range_get_start(start_bound, end_bound, direction);
+        let start = self
+            .range_get_start(start_bound, end_bound, direction)
+            .or_panic();
         NodeIteratorMut {
             current: start,
             direction,
@@ -327,7 +1355,7 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         start_bound: Bound<&K>,
         end_bound: Bound<&K>,
         direction: Direction,
-    ) -> Option<K> {
+    ) -> Result<Option<K>, AvlTreeError<K>> {
         // Get starting node, if it is inside the store we can derive the start in O(1).
         // If self.store.get(k) is Some, the bound is contained inside the store. So the start is either k or the next node.
         let start: Option<Option<K>> = match start_bound {
@@ -339,22 +1367,31 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         // When start is None we could not find the start bound directly in the store and we have to search in
         // the tree with find_first_node.
         // Afterwards we check if the starting node is inside the range.
-        start
-            .unwrap_or_else(|| self.find_first_node(start_bound, direction))
-            .filter(|s| end_bound.within_bound(&s, direction))
+        let start = match start {
+            Some(start) => start,
+            None => self.find_first_node(self.root.clone(), start_bound, direction)?,
+        };
+        Ok(start.filter(|s| end_bound.within_bound(s, direction)))
     }
 
     /// Finds the initial node within the specified range based on the given direction.
-    /// Iteratively traverses the tree and returns the most left or right node in the tree within the range.
+    /// Iteratively traverses the subtree of `start` and returns the most left or right node in it within the range.
     /// The direction parameter determines if it is left or right.
-    fn find_first_node(&self, start_bound: Bound<&K>, iterator_direction: Direction) -> Option<K> {
-        let mut current = self.root.clone();
+    pub(crate) fn find_first_node(
+        &self,
+        start: Option<K>,
+        start_bound: Bound<&K>,
+        iterator_direction: Direction,
+    ) -> Result<Option<K>, AvlTreeError<K>> {
+        let mut current = start;
         let mut result = None;
-        while current.is_some() {
+        while let Some(current_key) = current.as_ref() {
             let node = self
                 .store
-                .get(&current.clone().unwrap())
-                .expect("Node of subtree should exist.");
+                .get(current_key)
+                .ok_or_else(|| AvlTreeError::MissingNode {
+                    key: current_key.clone(),
+                })?;
             match start_bound.within_bound(&node.key, iterator_direction.opposite()) {
                 true => {
                     result = current.clone();
@@ -367,15 +1404,16 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
                 }
             }
         }
-        result
+        Ok(result)
     }
 
     /// Inserts a new key value pair into the tree.
     ///
     /// This function searches for an appropriate position for the key-value pair
-    /// in the tree. If the tree is empty, the key-value pair becomes the root.
+    /// in the subtree of `start`, which is the root or a node whose subtree contains the position.
+    /// If the tree is empty, the key-value pair becomes the root.
     /// Otherwise, it's inserted as a child of an existing node.
-    /// If the key already exists in the tree, the function panics.
+    /// If the key is found in the tree, although it is not in the KVStore, the node pointing to it is corrupted.
     ///
     /// Returns:
     /// - Some((K, Direction)): When the key-value pair is added to the tree,
@@ -383,29 +1421,40 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     ///   node was inserted.
     /// - None: When the tree is empty and the key-value pair becomes the root.
     ///
-    fn insert_node_in_empty_spot(&mut self, key: &K, value: V) -> Option<(K, Direction)> {
-        let mut current = self.root.clone();
+    fn insert_node_in_empty_spot(
+        &mut self,
+        start: Option<K>,
+        key: &K,
+        value: Option<V>,
+    ) -> Result<Option<(K, Direction)>, AvlTreeError<K>> {
+        let mut current = start;
         let mut parent = None;
         while let Some(parent_key) = current.as_ref() {
-            let current_node = self.get_node(parent_key).expect("Root should exist");
-            parent = current;
-            current = current_node
+            let current_node = self.node(parent_key)?;
+            let child = current_node
                 .get_child_in_key_direction(key)
-                .expect("Parent should have child in key direction")
+                .ok_or_else(|| AvlTreeError::CorruptedPointer {
+                    key: parent_key.clone(),
+                })?
                 .cloned();
+            parent = current;
+            current = child;
         }
         match parent {
             Some(parent_key) => {
-                let dir = Direction::from_ordering(key.cmp(&parent_key))
-                    .expect("Parent has to be different");
-                self.insert_node_and_adjust_pointers(&parent_key, key, value, dir);
-                Some((parent_key, dir))
+                let dir = Direction::from_ordering(key.cmp(&parent_key)).ok_or_else(|| {
+                    AvlTreeError::CorruptedPointer {
+                        key: parent_key.clone(),
+                    }
+                })?;
+                self.insert_node_and_adjust_pointers(&parent_key, key, value, dir)?;
+                Ok(Some((parent_key, dir)))
             }
             None => {
                 // Tree is empty
                 self.add_node(None, &key, value, None, None);
                 self.root = Some(key.clone());
-                None
+                Ok(None)
             }
         }
     }
@@ -413,47 +1462,72 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     /// Balance tree after inserting a node
     /// This function goes up the tree from the inserted node and balances a level if it is
     /// necessary.
+    /// It is also used after a join, where the subtree in the insert direction got one level deeper.
     ///
     /// parent_info: Tuple of the node above inserted node and direction of parent
-    fn balance_after_insert(&mut self, mut parent_info: Option<(K, Direction)>) {
-        let mut deepen = true;
-        while deepen && parent_info.is_some() {
-            let (node, insert_direction) = parent_info.unwrap();
-            let cached_node = self
-                .get_mut_node(&node)
-                .expect("Parent of insert should exist");
-            parent_info = cached_node
-                .parent
-                .clone()
-                .zip(cached_node.direction_to_parent().map(|d| d.opposite()));
-            if deepen {
-                deepen = cached_node.balance_factor == 0;
-                cached_node.balance_factor += insert_direction.direction_factor();
-            }
-            if cached_node.balance_factor.abs() == 2 {
-                self.balance(&node, insert_direction);
+    ///
+    /// Returns `true` if the whole tree got deeper.
+    fn balance_after_insert(
+        &mut self,
+        mut parent_info: Option<(K, Direction)>,
+    ) -> Result<bool, AvlTreeError<K>> {
+        while let Some((node, insert_direction)) = parent_info {
+            let cached_node = self.node_mut(&node)?;
+            let mut deepen = cached_node.balance_factor == 0;
+            cached_node.balance_factor += insert_direction.direction_factor();
+            let (balance_factor, parent) = (cached_node.balance_factor, cached_node.parent.clone());
+            let mut subtree = node;
+            if balance_factor.abs() == 2 {
+                // After an insert the rotation restores the depth of the subtree.
+                // After a join the deeper child can be balanced, then the rotated subtree stays one level deeper.
+                deepen = self.balance(&subtree, insert_direction)? != 0;
+                subtree = self.node(&subtree)?.parent.clone().ok_or_else(|| {
+                    AvlTreeError::CorruptedPointer {
+                        key: subtree.clone(),
+                    }
+                })?;
             }
             if !deepen {
-                break;
+                return Ok(false);
             }
+            // The subtree of the node got deeper, so the parent has to be updated as well.
+            parent_info = match parent {
+                Some(parent) => {
+                    let direction = self.direction_to_parent(&parent, &subtree)?.opposite();
+                    Some((parent, direction))
+                }
+                None => None,
+            };
         }
+        Ok(true)
     }
 
     /// Adds a new node to the primary store and a reference entry to the cache.
-    fn add_node(&mut self, parent: Option<K>, key: &K, value: V, prev: Option<K>, next: Option<K>) {
-        self.store.insert(
-            key.clone(),
-            Node {
-                key: key.clone(),
-                value,
-                left_child: None,
-                right_child: None,
-                next: next.clone(),
-                prev: prev.clone(),
-                parent: parent.clone(),
-                balance_factor: 0,
-            },
-        );
+    /// Without a value the node is already in the KVStore but in no tree, e.g. it was unlinked from another tree
+    /// of the same KVStore, then only its pointers are set in the cache and the stored value is kept.
+    fn add_node(
+        &mut self,
+        parent: Option<K>,
+        key: &K,
+        value: Option<V>,
+        prev: Option<K>,
+        next: Option<K>,
+    ) {
+        if let Some(value) = value {
+            self.store.insert(
+                key.clone(),
+                Node {
+                    key: key.clone(),
+                    value,
+                    left_child: None,
+                    right_child: None,
+                    next: next.clone(),
+                    prev: prev.clone(),
+                    parent: parent.clone(),
+                    balance_factor: 0,
+                },
+            );
+        }
         self.store_cache.insert(
             key.clone(),
             Node {
@@ -479,40 +1553,29 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         &mut self,
         parent_key: &K,
         key: &K,
-        value: V,
+        value: Option<V>,
         dir: Direction,
-    ) {
+    ) -> Result<(), AvlTreeError<K>> {
         // one neighbour in the double linked list is always the parent and the other is the next or prev of the parent, depending on the direction.
-        let other_neighbour = self
-            .get_node(parent_key)
-            .expect("Parent should exist")
-            .get_prev_next(dir);
+        let other_neighbour = self.node(parent_key)?.get_prev_next(dir);
         // If the other neighbour exists, update its pointer to the new node.
         if let Some(neighbour_key) = other_neighbour.clone() {
-            let neighbour = self
-                .get_mut_node(&neighbour_key)
-                .expect("Neighbour should exist");
+            let neighbour = self.node_mut(&neighbour_key)?;
             neighbour.set_prev_next(dir.opposite(), Some(key.clone()));
         }
 
         // Set the parent's child to the new node.
-        let parent = self.get_mut_node(parent_key).expect("Parent should exist");
+        let parent = self.node_mut(parent_key)?;
         parent.set_prev_next(dir, Some(key.clone()));
         parent.set_child(dir, Some(key.clone()));
 
-        // Find the prev and next of the new node.
-        let (prev, next) = match other_neighbour.clone() {
-            Some(neighbour) => {
-                let min_key = parent_key.clone().min(neighbour.clone());
-                let max_key = parent_key.clone().max(neighbour);
-                (Some(min_key), Some(max_key))
-            }
-            None => match dir {
-                Direction::Left => (None, Some(parent_key.clone())),
-                Direction::Right => (Some(parent_key.clone()), None),
-            },
+        // The new node is between the parent and the other neighbour.
+        let (prev, next) = match dir {
+            Direction::Left => (other_neighbour, Some(parent_key.clone())),
+            Direction::Right => (Some(parent_key.clone()), other_neighbour),
         };
         self.add_node(Some(parent_key.clone()), &key, value, prev, next);
+        Ok(())
     }
 
     /// Balances the tree following a node deletion.
@@ -534,36 +1597,44 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         &mut self,
         mut node_tuple: Option<(K, Direction)>,
         mut shortened: bool,
-    ) {
+    ) -> Result<(), AvlTreeError<K>> {
         while let Some((current_node, child_dir)) = node_tuple {
             if !shortened {
                 break;
             }
-            let parent_before_balance = self
-                .get_node(&current_node)
-                .cloned()
-                .expect("Node should exist because key was saved earlier")
-                .parent;
-            let (current_node_balance_factor, balance_child_direction) = {
-                let current_node = self
-                    .get_mut_node(&current_node)
-                    .expect("Node should exist because key was saved earlier");
+            let (current_node_balance_factor, parent_before_balance) = {
+                let current_node = self.node_mut(&current_node)?;
                 current_node.balance_factor += child_dir.direction_factor();
-                // get balance direction before balancing because the parent can change afterwards.
-                (
-                    current_node.balance_factor,
-                    current_node.direction_to_parent(),
-                )
+                (current_node.balance_factor, current_node.parent.clone())
             };
             let mut new_root_balance_factor = None;
 
             if current_node_balance_factor.abs() == 2 {
-                new_root_balance_factor = Some(self.balance(&current_node, child_dir.clone()));
+                new_root_balance_factor = Some(self.balance(&current_node, child_dir)?);
             }
             // continue going up if bf is 0 after removing of child -> layer was removed!
             shortened = new_root_balance_factor.unwrap_or(current_node_balance_factor) == 0;
-            node_tuple = parent_before_balance.zip(balance_child_direction);
+            if !shortened {
+                break;
+            }
+            // After a balance the subtree is rooted at the new parent of the current node, which took its place.
+            let subtree_root = match new_root_balance_factor {
+                Some(_) => self.node(&current_node)?.parent.clone().ok_or_else(|| {
+                    AvlTreeError::CorruptedPointer {
+                        key: current_node.clone(),
+                    }
+                })?,
+                None => current_node,
+            };
+            node_tuple = match parent_before_balance {
+                Some(parent) => {
+                    let direction = self.direction_to_parent(&parent, &subtree_root)?;
+                    Some((parent, direction))
+                }
+                None => None,
+            };
         }
+        Ok(())
     }
 
     /// Adjusts the tree structure after a node deletion.
@@ -584,31 +1655,37 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     ///       the parent of delete node, or the replacement node (if delete node was replaced).
     ///       `None` if the node had no parent (i.e., it was the root).
     ///     * A boolean indicating if the subtree was shortened as a result of the deletion.
-    fn rewire_tree_for_delete(&mut self, del_node_key: &K) -> (Option<(K, Direction)>, bool) {
-        let del_node = self
-            .get_node(del_node_key)
-            .expect("Node should be present, because this gets checked in the beginning of delete.")
-            .clone();
-        let del_node_parent_tuple = del_node.parent.clone().zip(del_node.direction_to_parent());
+    fn rewire_tree_for_delete(
+        &mut self,
+        del_node_key: &K,
+    ) -> Result<ParentAndShortened<K>, AvlTreeError<K>> {
+        let del_node = self.node(del_node_key)?.clone();
+        let del_node_parent_tuple = match del_node.parent.clone() {
+            Some(parent) => {
+                let direction = self.direction_to_parent(&parent, &del_node.key)?;
+                Some((parent, direction))
+            }
+            None => None,
+        };
         // rewire next and previous (if there is a replace node it is either next or previous so this works out without information about the replace node)
-        self.rewire_next_and_previous(&del_node);
+        self.rewire_next_and_previous(&del_node)?;
         let replace_node = self.calculate_replace_node(&del_node);
 
         let (replace_parent_tuple, shorten) = match replace_node.clone() {
-            Some(node) => Some(self.rewire_replace_node(&node, &del_node)).unzip(),
+            Some(node) => Some(self.rewire_replace_node(&node, &del_node)?).unzip(),
             None => (None, Some(true)),
         };
-        self.replace_del_node_in_parent(&del_node, replace_node.clone());
+        self.replace_del_node_in_parent(&del_node, replace_node.clone())?;
 
         // Check if the root has to be replaced.
         if self.root == Some(del_node.key.clone()) {
             self.root = replace_node;
         }
 
-        (
+        Ok((
             replace_parent_tuple.or(del_node_parent_tuple),
             shorten.unwrap_or(true),
-        )
+        ))
     }
 
     /// Given a node set for deletion (`del_node`), this function calculates
@@ -635,28 +1712,64 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     /// with the given replacement node key (`replace_node`).
     ///
     /// If `replace_node` is `None`, it effectively removes `del_node` from its parent's children.
-    fn replace_del_node_in_parent(&mut self, del_node: &Node<K, ()>, replace_node: Option<K>) {
+    fn replace_del_node_in_parent(
+        &mut self,
+        del_node: &Node<K, ()>,
+        replace_node: Option<K>,
+    ) -> Result<(), AvlTreeError<K>> {
         if let Some(parent_key) = &del_node.parent {
-            let parent_node = self
-                .get_mut_node(&parent_key)
-                .expect("Parent not in KVStore");
-            parent_node.replace_child(&del_node.key, replace_node);
+            let parent_node = self.node_mut(parent_key)?;
+            parent_node.replace_child(&del_node.key, replace_node)?;
         }
+        Ok(())
     }
 
     /// Remove delete node from double linked list.
     /// Does not need information about the replacement node, because it is either next or previous.
     /// So it will be correctly linked after this function.
-    fn rewire_next_and_previous(&mut self, del_node: &Node<K, ()>) {
+    fn rewire_next_and_previous(&mut self, del_node: &Node<K, ()>) -> Result<(), AvlTreeError<K>> {
         // Jump over del_node in next and previous.
-        del_node.next.as_ref().map(|next| {
-            self.get_mut_node(next).expect("Next is not in store").prev = del_node.prev.clone();
-        });
-        del_node.prev.as_ref().map(|prev| {
-            self.get_mut_node(prev)
-                .expect("Del node prev is not in store")
-                .next = del_node.next.clone();
-        });
+        if let Some(next) = del_node.next.as_ref() {
+            self.node_mut(next)?.prev = del_node.prev.clone();
+        }
+        if let Some(prev) = del_node.prev.as_ref() {
+            self.node_mut(prev)?.next = del_node.next.clone();
+        }
+        Ok(())
+    }
+
+    /// Stores the node under the new key at the same position in the tree and in the double linked list.
+    /// The new key has to be between the previous and the next key of the node.
+    fn rename_node(&mut self, old: &K, new: K) -> Result<(), AvlTreeError<K>> {
+        let node = self
+            .store_cache
+            .remove(old)
+            .ok_or_else(|| AvlTreeError::MissingNode { key: old.clone() })?;
+        match node.parent.as_ref() {
+            Some(parent) => self
+                .node_mut(parent)?
+                .replace_child(old, Some(new.clone()))?,
+            None => self.root = Some(new.clone()),
+        }
+        for child in [node.left_child.as_ref(), node.right_child.as_ref()]
+            .into_iter()
+            .flatten()
+        {
+            self.node_mut(child)?.parent = Some(new.clone());
+        }
+        if let Some(next) = node.next.as_ref() {
+            self.node_mut(next)?.prev = Some(new.clone());
+        }
+        if let Some(prev) = node.prev.as_ref() {
+            self.node_mut(prev)?.next = Some(new.clone());
+        }
+        let mut stored = self
+            .store
+            .remove(old)
+            .ok_or_else(|| AvlTreeError::MissingNode { key: old.clone() })?;
+        stored.key = new.clone();
+        self.store.insert(new, stored);
+        Ok(())
     }
 
     /// Reconfigures the tree structure after a node deletion, focusing on the replacement node.
@@ -682,16 +1795,14 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         &mut self,
         replace: &K,
         del_node: &Node<K, ()>,
-    ) -> ((K, Direction), bool) {
-        let replace = self.get_node(replace).expect("Node should exist.").clone();
-        let replace_child = self.rewire_replace_node_children(&replace, del_node);
+    ) -> Result<((K, Direction), bool), AvlTreeError<K>> {
+        let replace = self.node(replace)?.clone();
+        let replace_child = self.rewire_replace_node_children(&replace, del_node)?;
         let replace_parent_information =
-            self.rewire_replace_node_parent(&replace, &del_node, replace_child);
-        self.rewire_delete_node_child(del_node, &replace.key);
-        self.get_mut_node(&replace.key)
-            .expect("Replace should exist")
-            .parent = del_node.parent.clone();
-        replace_parent_information
+            self.rewire_replace_node_parent(&replace, del_node, replace_child)?;
+        self.rewire_delete_node_child(del_node, &replace.key)?;
+        self.node_mut(&replace.key)?.parent = del_node.parent.clone();
+        Ok(replace_parent_information)
     }
 
     /// Rewires the children of the replacement node when deleting a node from the tree.
@@ -710,17 +1821,15 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         &mut self,
         replace: &Node<K, ()>,
         del_node: &Node<K, ()>,
-    ) -> Option<K> {
+    ) -> Result<Option<K>, AvlTreeError<K>> {
         let replace_child = replace.left_child.clone().or(replace.right_child.clone());
         // rewire possible child of replace if replace and del_node are not parent and child.
         if replace.parent.as_ref() != Some(&del_node.key) {
-            replace_child.clone().map(|k| {
-                self.get_mut_node(&k)
-                    .expect("Replace child not in store but present in replace as child")
-                    .parent = replace.parent.clone();
-            });
+            if let Some(k) = replace_child.as_ref() {
+                self.node_mut(k)?.parent = replace.parent.clone();
+            }
         }
-        replace_child
+        Ok(replace_child)
     }
 
     /// Rewires the parent of the replacement node after deleting a node from the tree.
@@ -744,47 +1853,41 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         replace: &Node<K, ()>,
         del_node: &Node<K, ()>,
         replace_child: Option<K>,
-    ) -> ((K, Direction), bool) {
-        let mut replace_parent_key = replace
-            .parent
-            .clone()
-            .expect("should have parent because it is a child of the del_node.");
-        let mut replace_parent_direction = replace
-            .direction_to_parent()
-            .expect("should have parent because it is a child of the del_node.");
+    ) -> Result<((K, Direction), bool), AvlTreeError<K>> {
+        // The replace node should have a parent because it is in the subtree of the del_node.
+        let mut replace_parent_key =
+            replace
+                .parent
+                .clone()
+                .ok_or_else(|| AvlTreeError::CorruptedPointer {
+                    key: replace.key.clone(),
+                })?;
+        let mut replace_parent_direction =
+            self.direction_to_parent(&replace_parent_key, &replace.key)?;
         let shorten;
         if del_node.key == replace_parent_key {
             // if parent is node to delete, we do not have to rewrite stuff because node will be lost anyway.
             // change balance factor of replace because it will not be in the parent chain.
-            let replace = self
-                .get_mut_node(&replace.key)
-                .expect("Replace should exist");
-            let replace_balance_factor = del_node.balance_factor.clone()
-                + replace
-                    .direction_from_other(del_node.key.clone())
-                    .expect("Should have different keys")
-                    .direction_factor();
-            replace.balance_factor = replace_balance_factor;
+            // The del_node is the parent of replace, so it is in the direction to the parent.
+            let replace_balance_factor =
+                del_node.balance_factor + replace_parent_direction.direction_factor();
+            self.node_mut(&replace.key)?.balance_factor = replace_balance_factor;
             shorten = replace_balance_factor == 0;
-            del_node.parent.clone().map(|parent| {
+            if let Some(parent) = del_node.parent.clone() {
+                replace_parent_direction = self.direction_to_parent(&parent, &del_node.key)?;
                 replace_parent_key = parent;
-                replace_parent_direction = del_node.direction_to_parent().unwrap();
-            });
+            }
         } else {
             // Simply switch the pointer in replace parent with the child of replace.
-            let direction = replace.direction_to_parent().expect("Should have parent");
-            let replace_parent = self
-                .get_mut_node(&replace_parent_key)
-                .expect("Replace parent should exist");
-            replace_parent.replace_child(&replace.key, replace_child.clone());
+            let direction = replace_parent_direction;
+            let replace_parent = self.node_mut(&replace_parent_key)?;
+            replace_parent.replace_child(&replace.key, replace_child.clone())?;
             replace_parent.set_child(direction.opposite(), replace_child);
             // replace should max have one child so we have to rewire the leftover child:
-            self.get_mut_node(&replace.key)
-                .expect("Replace should exist")
-                .balance_factor = del_node.balance_factor;
+            self.node_mut(&replace.key)?.balance_factor = del_node.balance_factor;
             shorten = true;
         }
-        ((replace_parent_key, replace_parent_direction), shorten)
+        Ok(((replace_parent_key, replace_parent_direction), shorten))
     }
 
     /// Rewires the children of the node being deleted (`del_node`) to the replacement node (`replace`).
@@ -796,7 +1899,11 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     /// # Arguments
     /// * `del_node`: The node that is being deleted from the tree.
     /// * `replace`: The key of the node that is chosen as the replacement during deletion.
-    fn rewire_delete_node_child(&mut self, del_node: &Node<K, ()>, replace: &K) {
+    fn rewire_delete_node_child(
+        &mut self,
+        del_node: &Node<K, ()>,
+        replace: &K,
+    ) -> Result<(), AvlTreeError<K>> {
         let children: Vec<(K, Direction)> = [Direction::Left, Direction::Right]
             .into_iter()
             .map(|d| del_node.get_child(d).zip(Some(d)))
@@ -805,19 +1912,119 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
             .filter(|(k, _)| k != replace)
             .collect();
         if children.len() == 0 {
-            return;
+            return Ok(());
+        }
+        for (child, _) in children.iter() {
+            self.node_mut(child)?.parent = Some(replace.clone());
         }
-        children.iter().for_each(|(child, _)| {
-            self.get_mut_node(child)
-                .expect("Child of delete not in store but in tree")
-                .parent = Some(replace.clone());
-        });
         {
-            let replace_node = self.get_mut_node(replace).expect("Replace should exist");
+            let replace_node = self.node_mut(replace)?;
             children.into_iter().for_each(|(child, direction)| {
                 replace_node.set_child(direction, Some(child));
             });
         }
+        Ok(())
+    }
+
+    /// Returns the height of the subtree, following the deeper child from the root.
+    fn height(&mut self, mut current: Option<K>) -> Result<i32, AvlTreeError<K>> {
+        let mut height = 0;
+        while let Some(key) = current {
+            let node = self.node(&key)?;
+            height += 1;
+            current = match node.balance_factor < 0 {
+                true => node.left_child.clone(),
+                false => node.right_child.clone(),
+            };
+        }
+        Ok(height)
+    }
+
+    /// Splits the subtree into the nodes left and right of a split point, both are valid AVL trees afterwards.
+    /// `locate` returns `Less` for nodes left of the split point and `Greater` for nodes right of it.
+    /// A node for which it returns `Equal` is detached from both parts, its pointers are not updated.
+    /// The double linked list is not changed.
+    fn split<F: Fn(&K) -> Ordering>(
+        &mut self,
+        subtree: Subtree<K>,
+        locate: &F,
+    ) -> Result<(Subtree<K>, Subtree<K>), AvlTreeError<K>> {
+        let (Some(root), height) = subtree else {
+            return Ok(((None, 0), (None, 0)));
+        };
+        let node = self.node(&root)?;
+        let (left, right) = (node.left_child.clone(), node.right_child.clone());
+        let left_height = height - 1 - (node.balance_factor > 0) as i32;
+        let right_height = height - 1 - (node.balance_factor < 0) as i32;
+        for child in [left.as_ref(), right.as_ref()].into_iter().flatten() {
+            self.node_mut(child)?.parent = None;
+        }
+        match locate(&root) {
+            Ordering::Equal => Ok(((left, left_height), (right, right_height))),
+            Ordering::Less => {
+                let (right_left, right_right) = self.split((right, right_height), locate)?;
+                let joined = self.join((left, left_height), root, right_left)?;
+                Ok(((Some(joined.0), joined.1), right_right))
+            }
+            Ordering::Greater => {
+                let (left_left, left_right) = self.split((left, left_height), locate)?;
+                let joined = self.join(left_right, root, (right, right_height))?;
+                Ok((left_left, (Some(joined.0), joined.1)))
+            }
+        }
+    }
+
+    /// Joins two detached subtrees with the middle node between them, all keys of `left` are smaller than
+    /// the middle key and all keys of `right` are bigger.
+    /// The middle node is attached at the border of the deeper subtree, where the depth of the other subtree is reached,
+    /// and the path above it is balanced like after an insert.
+    /// Returns the root and the height of the joined tree. The double linked list is not changed.
+    fn join(
+        &mut self,
+        left: Subtree<K>,
+        middle: K,
+        right: Subtree<K>,
+    ) -> Result<(K, i32), AvlTreeError<K>> {
+        let (direction, (deeper, deeper_height), (other, other_height)) = match left.1 > right.1 {
+            true => (Direction::Right, left, right),
+            false => (Direction::Left, right, left),
+        };
+        // Go down the border of the deeper subtree to the spot of the middle node.
+        let mut parent = None;
+        let mut current = deeper.clone();
+        let mut height = deeper_height;
+        while height > other_height + 1 {
+            let key = current.ok_or_else(|| AvlTreeError::CorruptedPointer {
+                key: middle.clone(),
+            })?;
+            let node = self.node(&key)?;
+            height -= match node.balance_factor * direction.direction_factor() < 0 {
+                true => 2,
+                false => 1,
+            };
+            current = node.get_child(direction);
+            parent = Some(key);
+        }
+        let middle_node = self.node_mut(&middle)?;
+        middle_node.set_child(direction.opposite(), current.clone());
+        middle_node.set_child(direction, other.clone());
+        middle_node.parent = parent.clone();
+        middle_node.balance_factor = (other_height - height) * direction.direction_factor();
+        for child in [current.as_ref(), other.as_ref()].into_iter().flatten() {
+            self.node_mut(child)?.parent = Some(middle.clone());
+        }
+        let Some(parent) = parent else {
+            return Ok((middle, other_height.max(height) + 1));
+        };
+        self.node_mut(&parent)?
+            .set_child(direction, Some(middle.clone()));
+        // The rotations at the root of the deeper subtree update the root of the tree.
+        self.root = deeper;
+        let deepened = self.balance_after_insert(Some((parent, direction)))?;
+        let root = self.root.clone().ok_or_else(|| AvlTreeError::MissingNode {
+            key: middle.clone(),
+        })?;
+        Ok((root, deeper_height + deepened as i32))
     }
 
     /// Balances the subtree rooted at `root` by performing AVL rotations.
@@ -832,16 +2039,13 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     ///
     /// # Returns
     /// Returns the new balance factor of the node after the rotations.
-    fn balance(&mut self, root: &K, balance_direction: Direction) -> i32 {
+    fn balance(&mut self, root: &K, balance_direction: Direction) -> Result<i32, AvlTreeError<K>> {
+        // The heavier side of an unbalanced node has a child.
         let child_id = self
-            .get_node(root)
-            .expect("Node should exist")
+            .node(root)?
             .get_child(balance_direction)
-            .expect("Child should exist");
-        let child_balance_factor = self
-            .get_node(&child_id)
-            .expect("Child should exist")
-            .balance_factor;
+            .ok_or_else(|| AvlTreeError::CorruptedPointer { key: root.clone() })?;
+        let child_balance_factor = self.node(&child_id)?.balance_factor;
         if child_balance_factor.signum() == balance_direction.direction_factor() {
             self.balance_with_subtree_in_same_direction(root, &child_id, balance_direction)
         } else if child_balance_factor == 0 {
@@ -862,7 +2066,7 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         root: &K,
         child: &K,
         imbalance_direction: Direction,
-    ) -> i32 {
+    ) -> Result<i32, AvlTreeError<K>> {
         /*
          *  Before Balance:
          *      R
@@ -880,15 +2084,11 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
          *  / \ / \
          * A  A A  A
          */
-        self.get_mut_node(child)
-            .expect("Child in balance should exist")
-            .balance_factor = 0;
-        self.get_mut_node(root)
-            .expect("Balance root should exist")
-            .balance_factor = 0;
-        self.rotate(imbalance_direction.opposite(), root, child);
+        self.node_mut(child)?.balance_factor = 0;
+        self.node_mut(root)?.balance_factor = 0;
+        self.rotate(imbalance_direction.opposite(), root, child)?;
         // Balance_factor of new root=child=0
-        0
+        Ok(0)
     }
 
     /// Performs a single AVL rotation when the balance factor of the child causing imbalance is zero.
@@ -903,7 +2103,7 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         root: &K,
         child: &K,
         imbalance_direction: Direction,
-    ) -> i32 {
+    ) -> Result<i32, AvlTreeError<K>> {
         /*
          * imbalance direction = right
          *  Before Balance :
@@ -926,15 +2126,11 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
          * -> C.bf = -1, R.bf = -1
          *
          */
-        self.get_mut_node(root)
-            .expect("Root in balance should exist")
-            .balance_factor = imbalance_direction.direction_factor();
-        self.get_mut_node(child)
-            .expect("Child in balance should exist")
-            .balance_factor = imbalance_direction.opposite().direction_factor();
-        self.rotate(imbalance_direction.opposite(), root, child);
+        self.node_mut(root)?.balance_factor = imbalance_direction.direction_factor();
+        self.node_mut(child)?.balance_factor = imbalance_direction.opposite().direction_factor();
+        self.rotate(imbalance_direction.opposite(), root, child)?;
         // Balance_factor of new root=child
-        imbalance_direction.opposite().direction_factor()
+        Ok(imbalance_direction.opposite().direction_factor())
     }
 
     /// Performs a double AVL rotation to correct imbalances caused by a grandchild.
@@ -949,7 +2145,7 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         root: &K,
         child: &K,
         imbalance_direction: Direction,
-    ) -> i32 {
+    ) -> Result<i32, AvlTreeError<K>> {
         /*
          * imbalance direction = right
          *  Before Balance :
@@ -972,14 +2168,11 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
          */
         // This reference is not synced with the parents list. However, this child node should be further down in the tree and not in the parents list
         let new_root = self
-            .get_node(child)
-            .expect("Child of balance should exist")
+            .node(child)?
             .get_child(imbalance_direction.opposite())
-            .unwrap();
+            .ok_or_else(|| AvlTreeError::CorruptedPointer { key: child.clone() })?;
         let new_root_balance_factor = {
-            let new_root_node = self
-                .get_mut_node(&new_root)
-                .expect("New root should also exist else we would not be in this case!");
+            let new_root_node = self.node_mut(&new_root)?;
             mem::replace(&mut new_root_node.balance_factor, 0)
         };
 
@@ -987,15 +2180,15 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
             root,
             imbalance_direction,
             new_root_balance_factor,
-        );
+        )?;
         self.change_bf_based_on_imbalance_direction(
             child,
             imbalance_direction.opposite(),
             new_root_balance_factor,
-        );
-        self.rotate(imbalance_direction, child, &new_root);
-        self.rotate(imbalance_direction.opposite(), root, &new_root);
-        0
+        )?;
+        self.rotate(imbalance_direction, child, &new_root)?;
+        self.rotate(imbalance_direction.opposite(), root, &new_root)?;
+        Ok(0)
     }
 
     /// Updates the balance factor of a node based on the balance factor of the new root and the direction of imbalance.
@@ -1008,14 +2201,13 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
         node_id: &K,
         direction: Direction,
         new_root_balance_factor: i32,
-    ) {
-        let root = self
-            .get_mut_node(node_id)
-            .expect("Root in balance should exist");
+    ) -> Result<(), AvlTreeError<K>> {
+        let root = self.node_mut(node_id)?;
         root.balance_factor = match new_root_balance_factor == direction.direction_factor() {
             false => 0,
             true => direction.opposite().direction_factor(),
         };
+        Ok(())
     }
 
     /// Performs a tree rotation
@@ -1025,7 +2217,12 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
     /// The left child of root is exchanged with the right child of child or vice versa.
     /// With this one node moves into the left subtree from the right subtree or vice versa.
     /// Thus the balance factor of the subtree reduces by one or increases by one.
-    fn rotate(&mut self, rotate_direction: Direction, root: &K, child: &K) {
+    fn rotate(
+        &mut self,
+        rotate_direction: Direction,
+        root: &K,
+        child: &K,
+    ) -> Result<(), AvlTreeError<K>> {
         /*
            *  Rotate left:
            *      R
@@ -1054,67 +2251,214 @@ impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor
            *      / \
            *     LR  _
         */
-        let parent_key = self.rotate_rewire_parent(root, child);
+        let parent_key = self.rotate_rewire_parent(root, child)?;
         if parent_key.is_none() {
             self.root = Some(child.clone());
         }
         let left_over_child;
         {
-            let child = self
-                .get_mut_node(child)
-                .expect("Rotate without child at right position");
+            let child = self.node_mut(child)?;
             child.parent = parent_key;
             left_over_child = child.get_child(rotate_direction);
             child.set_child(rotate_direction, Some(root.clone()));
         }
         if let Some(old_root_child_key) = left_over_child.as_ref() {
-            self.get_mut_node(old_root_child_key)
-                .expect("Child of child not in store")
-                .parent = Some(root.clone());
+            self.node_mut(old_root_child_key)?.parent = Some(root.clone());
         }
-        let root = self
-            .get_mut_node(root)
-            .expect("Rotate without root in Store");
+        let root = self.node_mut(root)?;
         root.set_child(rotate_direction.opposite(), left_over_child);
         root.parent = Some(child.clone());
+        Ok(())
+    }
+
+    fn rotate_rewire_parent(&mut self, root: &K, child: &K) -> Result<Option<K>, AvlTreeError<K>> {
+        let parent = self.node(root)?.parent.clone();
+        if let Some(parent) = parent.as_ref() {
+            self.node_mut(parent)?
+                .replace_child(root, Some(child.clone()))?;
+        }
+        Ok(parent)
+    }
+}
+
+/// State of a bulk load that is spread over multiple transactions.
+/// It is stored in the component between the chunks, see `AvlTree::bulk_load`.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct BulkLoad<K: ScryptoSbor> {
+    /// The last loaded key, which is the tail of the tree if the tree was not changed in between.
+    tail: Option<K>,
+    /// The number of entries loaded so far.
+    loaded: u64,
+}
+
+impl<K: ScryptoSbor> Default for BulkLoad<K> {
+    fn default() -> Self {
+        BulkLoad::new()
+    }
+}
+
+impl<K: ScryptoSbor> BulkLoad<K> {
+    pub fn new() -> Self {
+        BulkLoad {
+            tail: None,
+            loaded: 0,
+        }
+    }
+
+    pub fn loaded(&self) -> u64 {
+        self.loaded
+    }
+}
+
+/// State of a bulk delete that is spread over multiple transactions.
+/// It is stored in the component between the calls, see `AvlTree::bulk_delete`.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct BulkDelete<K: ScryptoSbor> {
+    /// The next key to delete, which is the head of the tree if the tree was not changed in between.
+    head: Option<K>,
+    /// The number of entries removed so far.
+    removed: u64,
+}
+
+impl<K: ScryptoSbor> Default for BulkDelete<K> {
+    fn default() -> Self {
+        BulkDelete::new()
+    }
+}
+
+impl<K: ScryptoSbor> BulkDelete<K> {
+    pub fn new() -> Self {
+        BulkDelete {
+            head: None,
+            removed: 0,
+        }
+    }
+
+    pub fn removed(&self) -> u64 {
+        self.removed
+    }
+}
+
+/// Serializable bound of a `RangeCursor`, analogue to `Bound`.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum CursorBound<K: ScryptoSbor> {
+    Included(K),
+    Excluded(K),
+    Unbounded,
+}
+
+impl<K: ScryptoSbor + Clone> CursorBound<K> {
+    fn from_bound(bound: Bound<&K>) -> Self {
+        match bound {
+            Bound::Included(k) => CursorBound::Included(k.clone()),
+            Bound::Excluded(k) => CursorBound::Excluded(k.clone()),
+            Bound::Unbounded => CursorBound::Unbounded,
+        }
+    }
+
+    fn as_bound(&self) -> Bound<&K> {
+        match self {
+            CursorBound::Included(k) => Bound::Included(k),
+            CursorBound::Excluded(k) => Bound::Excluded(k),
+            CursorBound::Unbounded => Bound::Unbounded,
+        }
+    }
+}
+
+/// Position inside a range, that can be stored or returned from a method and continued later,
+/// e.g. to page through a big tree over multiple transactions, see `AvlTree::range_page`.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub struct RangeCursor<K: ScryptoSbor> {
+    /// The bound from where the iteration continues.
+    from: CursorBound<K>,
+    /// The bound where the iteration stops.
+    to: CursorBound<K>,
+    /// The direction of the iteration.
+    direction: Direction,
+}
+
+impl<K: ScryptoSbor + Clone> RangeCursor<K> {
+    /// Creates a cursor at the beginning of the range, that iterates like `range`.
+    pub fn new<R: RangeBounds<K>>(range: R) -> Self {
+        RangeCursor {
+            from: CursorBound::from_bound(range.start_bound()),
+            to: CursorBound::from_bound(range.end_bound()),
+            direction: Direction::Right,
+        }
+    }
+
+    /// Creates a cursor at the end of the range, that iterates like `range_back`.
+    pub fn new_back<R: RangeBounds<K>>(range: R) -> Self {
+        RangeCursor {
+            from: CursorBound::from_bound(range.end_bound()),
+            to: CursorBound::from_bound(range.start_bound()),
+            direction: Direction::Left,
+        }
     }
 
-    fn rotate_rewire_parent(&mut self, root: &K, child: &K) -> Option<K> {
-        let parent = self
-            .get_node(root)
-            .expect("rewire Node should exist")
-            .parent
-            .clone();
-        parent.as_ref().map(|parent| {
-            self.get_mut_node(parent)
-                .expect("Parent of rotate root not in store")
-                .replace_child(root, Some(child.clone()))
-        });
-        parent
+    /// The bound from where the iteration continues.
+    pub fn from(&self) -> &CursorBound<K> {
+        &self.from
+    }
+
+    /// The bound where the iteration stops.
+    pub fn to(&self) -> &CursorBound<K> {
+        &self.to
+    }
+
+    /// Returns `true` if the cursor iterates backwards.
+    pub fn is_back(&self) -> bool {
+        self.direction == Direction::Left
     }
 }
 
+/// A node of the tree as it is stored in the KVStore, keyed by its key.
 #[derive(ScryptoSbor, Clone)]
-pub(crate) struct Node<K: ScryptoSbor, V: ScryptoSbor> {
+pub struct Node<K: ScryptoSbor, V: ScryptoSbor> {
     /// Unique key for this node
-    pub(crate) key: K,
-    pub(crate) value: V,
+    pub key: K,
+    /// The value stored under the key
+    pub value: V,
     /// The left and right children of this node in the tree
-    pub(crate) left_child: Option<K>,
-    pub(crate) right_child: Option<K>,
+    pub left_child: Option<K>,
+    pub right_child: Option<K>,
     /// The parent of this node in the tree
-    pub(crate) parent: Option<K>,
+    pub parent: Option<K>,
     /// The next and previous nodes in double linked list. The double linked list is ordered by the keys.
     /// So to get a sorted list of all keys, we can iterate over these pointers.
-    pub(crate) next: Option<K>,
-    pub(crate) prev: Option<K>,
+    pub next: Option<K>,
+    pub prev: Option<K>,
     /// Balance factor: height of right subtree - height of left subtree.
     /// The heights are never calculated, but the balance factor is updated
     /// based on the operations (insert, delete, balance) in the tree.
-    pub(crate) balance_factor: i32,
+    pub balance_factor: i32,
 }
 
 impl<K: ScryptoSbor + Clone + Eq + Ord + Display + Debug, V: ScryptoSbor> Node<K, V> {
+    /// Returns the pointers and the balance factor without the value, as they are cached.
+    fn navigation(&self) -> Node<K, ()> {
+        Node {
+            key: self.key.clone(),
+            value: (),
+            left_child: self.left_child.clone(),
+            right_child: self.right_child.clone(),
+            parent: self.parent.clone(),
+            prev: self.prev.clone(),
+            next: self.next.clone(),
+            balance_factor: self.balance_factor,
+        }
+    }
+
+    /// Copy the pointers and the balance factor of the cached node
+    fn set_navigation(&mut self, cached: &Node<K, ()>) {
+        self.left_child = cached.left_child.clone();
+        self.right_child = cached.right_child.clone();
+        self.parent = cached.parent.clone();
+        self.prev = cached.prev.clone();
+        self.next = cached.next.clone();
+        self.balance_factor = cached.balance_factor;
+    }
+
     /// Change the pointer of the child of this node in the given direction
     fn set_child(&mut self, direction: Direction, child: Option<K>) {
         match direction {
@@ -1128,14 +2472,22 @@ impl<K: ScryptoSbor + Clone + Eq + Ord + Display + Debug, V: ScryptoSbor> Node<K
     }
 
     /// Replace the child of this node based on the old child.
-    fn replace_child(&mut self, old_child: &K, new_child: Option<K>) {
+    /// Returns `AvlTreeError::CorruptedPointer` if the old child is not a child of this node.
+    fn replace_child(
+        &mut self,
+        old_child: &K,
+        new_child: Option<K>,
+    ) -> Result<(), AvlTreeError<K>> {
         if self.left_child == Some(old_child.clone()) {
             self.left_child = new_child;
         } else if self.right_child == Some(old_child.clone()) {
             self.right_child = new_child;
         } else {
-            panic!("Tried to overwrite node but was not a child");
+            return Err(AvlTreeError::CorruptedPointer {
+                key: old_child.clone(),
+            });
         }
+        Ok(())
     }
 
     /// Get the child of this node in the given direction
@@ -1190,20 +2542,6 @@ impl<K: ScryptoSbor + Clone + Eq + Ord + Display + Debug, V: ScryptoSbor> Node<K
         }
     }
 
-    /// Determines the node's direction relative to its parent.
-    /// Returns `Some(Direction)` indicating whether this node is to the left or right of its parent, or `None` if there's no parent.
-    fn direction_to_parent(&self) -> Option<Direction> {
-        self.parent.as_ref().map(|parent| {
-            Direction::from_ordering(parent.cmp(&self.key)).expect("Nodes should be unequal")
-        })
-    }
-
-    /// Determines the direction of another node relative to this node.
-    /// Returns `Some(Direction)` indicating whether the other node is to the left or right of this node.
-    fn direction_from_other(&self, other: K) -> Option<Direction> {
-        Some(Direction::from_ordering(other.cmp(&self.key)).expect("Nodes should be unequal"))
-    }
-
     /// Retrieves the next node's key in the linked list in the specified direction.
     /// Returns the key of the node in the given direction or `None` if there's no such node.
     fn next(&self, direction: Direction) -> Option<K> {
@@ -1214,6 +2552,18 @@ impl<K: ScryptoSbor + Clone + Eq + Ord + Display + Debug, V: ScryptoSbor> Node<K
     }
 }
 
+/// A reference to the value of a node, that holds the lock on the node in the KVStore.
+///
+/// Like `ItemRefMut` it borrows the tree, so changing the tree while it is alive does not compile:
+/// ```compile_fail,E0502
+/// use scrypto_avltree::AvlTree;
+///
+/// fn read(tree: &mut AvlTree<i32, i32>) -> i32 {
+///     let value = tree.get(&1).unwrap();
+///     tree.remove(&2);
+///     *value
+/// }
+/// ```
 pub struct ItemRef<'a, K: ScryptoSbor, V: ScryptoSbor> {
     item: KeyValueEntryRef<'a, Node<K, V>>,
 }
@@ -1235,6 +2585,31 @@ impl<K: ScryptoSbor, V: ScryptoSbor> ItemRef<'_, K, V> {
     }
 }
 
+/// A mutable reference to the value of a node, that holds the lock on the node in the KVStore.
+///
+/// The reference borrows the tree, so the tree can not be changed while the node is locked.
+/// An `insert` or `remove` while the reference is alive is rejected by the compiler,
+/// so it never reaches the engine as a substate lock error:
+/// ```compile_fail,E0499
+/// use scrypto_avltree::AvlTree;
+///
+/// fn update(tree: &mut AvlTree<i32, i32>) {
+///     let mut value = tree.get_mut(&1).unwrap();
+///     tree.insert(2, 2);
+///     *value = 2;
+/// }
+/// ```
+/// Dropping the reference first releases the lock:
+/// ```
+/// use scrypto_avltree::AvlTree;
+///
+/// fn update(tree: &mut AvlTree<i32, i32>) {
+///     let mut value = tree.get_mut(&1).unwrap();
+///     *value = 2;
+///     drop(value);
+///     tree.insert(2, 2);
+/// }
+/// ```
 pub struct ItemRefMut<'a, K: ScryptoSbor, V: ScryptoSbor> {
     item: KeyValueEntryRefMut<'a, Node<K, V>>,
 }
@@ -1269,8 +2644,9 @@ impl<'a, K: ScryptoSbor, V: ScryptoSbor> DerefMut for ItemRefMut<'a, K, V> {
 }
 
 /// Represents a direction, either `Left` or `Right`.
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Direction {
+/// As iteration direction `Right` is ascending and `Left` is descending.
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
     Left,
     Right,
 }
@@ -1320,29 +2696,140 @@ impl Direction {
 /// nodes by their keys. Each iteration fetches the node's value, advancing
 /// the iterator based on the direction until the boundary is reached.
 ///
+/// The iterator is double ended, the back is only searched when it is used the first time.
+/// Both ends stop as soon as they meet.
+///
 /// # Parameters
 /// - `current`: The key of the current node to begin iterating from.
+/// - `back`: The key of the current node at the back, `None` as long as the back was not used.
 /// - `direction`: The direction to move in the linked list (`Left` or `Right`).
+/// - `start`: The boundary key to stop the iteration from the back.
 /// - `end`: The boundary key to stop iteration.
-/// - `store`: The reference to the key-value store containing the linked nodes.
-pub struct NodeIterator<'a, K: ScryptoSbor, V: ScryptoSbor> {
+/// - `tree`: The reference to the tree containing the linked nodes.
+pub struct NodeIterator<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
     current: Option<K>,
+    back: Option<Option<K>>,
     direction: Direction,
+    start: Bound<K>,
     end: Bound<K>,
-    store: &'a KeyValueStore<K, Node<K, V>>,
+    tree: &'a AvlTree<K, V>,
 }
 
-impl<K: ScryptoSbor + Clone, V: ScryptoSbor> NodeIterator<'_, K, V> {
+impl<K: ScryptoSbor + Clone + Eq + Ord + Hash, V: ScryptoSbor> NodeIterator<'_, K, V> {
     pub fn has_next(&self) -> bool {
         self.current.is_some()
     }
     pub fn next_key(&self) -> Option<K> {
         self.current.clone()
     }
+    /// Returns the direction of the iteration, `Direction::Right` for ascending keys.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns a cursor to continue the iteration later from the next key, see `AvlTree::range_from_cursor`.
+    /// Returns `None` if the iteration is finished.
+    pub fn cursor(&self) -> Option<RangeCursor<K>> {
+        let current = self.current.clone()?;
+        let to = match &self.back {
+            Some(Some(back)) => CursorBound::Included(back.clone()),
+            _ => CursorBound::from_bound(self.end.as_ref()),
+        };
+        Some(RangeCursor {
+            from: CursorBound::Included(current),
+            to,
+            direction: self.direction,
+        })
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Hash + Display + Debug, V: ScryptoSbor>
+    NodeIterator<'a, K, V>
+{
+    /// Advances the iterator and returns a reference to the stored node without cloning anything.
+    /// Only the `next` or `prev` pointer of the node is read to move on.
+    pub(crate) fn next_node(&mut self) -> Option<KeyValueEntryRef<'a, Node<K, V>>> {
+        self.try_next_node().map(OrPanic::or_panic)
+    }
+
+    /// Advances the iterator like `next_node`, a node that is missing in the KVStore ends the iteration with an error.
+    pub(crate) fn try_next_node(
+        &mut self,
+    ) -> Option<Result<StoredNode<'a, K, V>, AvlTreeError<K>>> {
+        let current_key = self.current.take()?;
+        let Some(node) = self.tree.store.get(&current_key) else {
+            self.back = Some(None);
+            return Some(Err(AvlTreeError::MissingNode { key: current_key }));
+        };
+        if self.back.as_ref() == Some(&Some(current_key)) {
+            // Front and back met, the iterator is exhausted on both ends.
+            self.back = Some(None);
+            return Some(Ok(node));
+        }
+        let next_key = node.next(self.direction);
+        self.current = match next_key
+            .as_ref()
+            .map(|k| self.end.as_ref().within_bound(k, self.direction))
+        {
+            Some(true) => next_key,
+            _ => {
+                self.back = Some(None);
+                None
+            }
+        };
+        Some(Ok(node))
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Hash + Display + Debug, V: ScryptoSbor + Clone>
+    NodeIterator<'a, K, V>
+{
+    /// Returns the next key from the back, if the back was already used.
+    pub(crate) fn next_back_key(&self) -> Option<K> {
+        self.back.clone().flatten()
+    }
+
+    /// Advances the iterator from the back and returns a reference to the stored node.
+    /// The back is searched with `range_get_start` the first time it is used.
+    pub(crate) fn next_back_node(&mut self) -> Option<KeyValueEntryRef<'a, Node<K, V>>> {
+        let back_direction = self.direction.opposite();
+        let back = match self.back.take() {
+            Some(back) => back,
+            None => self
+                .tree
+                .range_get_start(self.end.as_ref(), self.start.as_ref(), back_direction)
+                .or_panic(),
+        };
+        let Some(back_key) = back else {
+            self.back = Some(None);
+            return None;
+        };
+        let node = self.tree.store.get(&back_key).expect("Node not found");
+        if self.current.as_ref() == Some(&back_key) {
+            // Front and back met, the iterator is exhausted on both ends.
+            self.current = None;
+            self.back = Some(None);
+            return Some(node);
+        }
+        let next_key = node.next(back_direction);
+        self.back = Some(
+            match next_key
+                .as_ref()
+                .map(|k| self.start.as_ref().within_bound(k, back_direction))
+            {
+                Some(true) => next_key,
+                _ => {
+                    self.current = None;
+                    None
+                }
+            },
+        );
+        Some(node)
+    }
 }
 
-impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Display + Debug, V: ScryptoSbor + Clone> Iterator
-    for NodeIterator<'a, K, V>
+impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Hash + Display + Debug, V: ScryptoSbor + Clone>
+    Iterator for NodeIterator<'a, K, V>
 {
     type Item = (K, V, Option<K>);
 
@@ -1353,19 +2840,99 @@ impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Display + Debug, V: ScryptoSbor + C
     /// that node. If no such node exists, or it is outside the boundary, the iterator
     /// stops and returns `None` on subsequent calls.
     fn next(&mut self) -> Option<Self::Item> {
-        let current_key = self.current.clone()?;
-        let node = self.store.get(&current_key).expect("Node not found");
-        let next_key = node.next(self.direction);
-        self.current = match next_key
-            .as_ref()
-            .map(|k| self.end.as_ref().within_bound(k, self.direction))
-        {
-            Some(true) => next_key,
-            _ => None,
-        };
-        Some((current_key, node.value.clone(), self.current.clone()))
+        let node = self.next_node()?;
+        Some((node.key.clone(), node.value.clone(), self.current.clone()))
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Hash + Display + Debug, V: ScryptoSbor + Clone>
+    DoubleEndedIterator for NodeIterator<'a, K, V>
+{
+    /// Advances the iterator from the back and returns the value.
+    /// The returned next key is the following key from the back.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.next_back_node()?;
+        Some((node.key.clone(), node.value.clone(), self.next_back_key()))
+    }
+}
+
+/// `KeyIterator` iterates over the keys of a range in the same order as `NodeIterator`,
+/// but never clones the values of the nodes it passes.
+pub struct KeyIterator<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    nodes: NodeIterator<'a, K, V>,
+}
+
+impl<K: ScryptoSbor + Clone + Eq + Ord + Hash, V: ScryptoSbor> KeyIterator<'_, K, V> {
+    pub fn has_next(&self) -> bool {
+        self.nodes.has_next()
+    }
+    pub fn next_key(&self) -> Option<K> {
+        self.nodes.next_key()
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Hash + Display + Debug, V: ScryptoSbor> Iterator
+    for KeyIterator<'a, K, V>
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next_node().map(|node| node.key.clone())
+    }
+}
+/// `NodeRefIterator` iterates over a range in the same order as `NodeIterator`,
+/// but yields the values as `ItemRef` instead of cloning them.
+pub struct NodeRefIterator<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    nodes: NodeIterator<'a, K, V>,
+}
+
+impl<K: ScryptoSbor + Clone + Eq + Ord + Hash, V: ScryptoSbor> NodeRefIterator<'_, K, V> {
+    pub fn has_next(&self) -> bool {
+        self.nodes.has_next()
+    }
+    pub fn next_key(&self) -> Option<K> {
+        self.nodes.next_key()
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Hash + Display + Debug, V: ScryptoSbor> Iterator
+    for NodeRefIterator<'a, K, V>
+{
+    type Item = (K, ItemRef<'a, K, V>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes
+            .next_node()
+            .map(|node| (node.key.clone(), ItemRef { item: node }))
+    }
+}
+
+/// `TryNodeIterator` iterates over a range in the same order as `NodeIterator`, see `AvlTree::try_range`.
+/// A node that is missing in the KVStore is yielded as an error and ends the iteration.
+pub struct TryNodeIterator<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    nodes: NodeIterator<'a, K, V>,
+}
+
+impl<K: ScryptoSbor + Clone + Eq + Ord + Hash, V: ScryptoSbor> TryNodeIterator<'_, K, V> {
+    pub fn has_next(&self) -> bool {
+        self.nodes.has_next()
+    }
+    pub fn next_key(&self) -> Option<K> {
+        self.nodes.next_key()
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Hash + Display + Debug, V: ScryptoSbor + Clone>
+    Iterator for TryNodeIterator<'a, K, V>
+{
+    type Item = Result<(K, V, Option<K>), AvlTreeError<K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.try_next_node()?;
+        Some(node.map(|node| (node.key.clone(), node.value.clone(), self.nodes.next_key())))
     }
 }
+
 pub enum IterMutControl {
     Continue,
     Break,
@@ -1413,7 +2980,7 @@ impl<'a, K: ScryptoSbor + Clone + Ord + Eq + Display + Debug, V: ScryptoSbor + C
     }
 }
 
-trait WithinBound<K> {
+pub(crate) trait WithinBound<K> {
     fn within_bound(&self, key: &K, direction: Direction) -> bool;
 }
 
@@ -0,0 +1,44 @@
+use scrypto::prelude::*;
+
+/// The error of the fallible `try_*` methods of the `AvlTree`.
+///
+/// `MissingNode` and `CorruptedPointer` mean that the stored tree is inconsistent,
+/// they are returned instead of aborting the transaction, so a component can report which key is affected.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq)]
+pub enum AvlTreeError<K: ScryptoSbor> {
+    /// A pointer of the tree references a key that is not in the KVStore.
+    MissingNode { key: K },
+    /// The pointers of the node contradict the structure of the tree, e.g. the node is not a child of its parent.
+    CorruptedPointer { key: K },
+    /// The key of the operation is not in the tree.
+    KeyNotFound { key: K },
+    /// The key of the operation is already in the tree.
+    KeyExists { key: K },
+}
+
+impl<K: ScryptoSbor + Display> Display for AvlTreeError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AvlTreeError::MissingNode { key } => {
+                write!(f, "AvlTree: node {} is referenced but not stored", key)
+            }
+            AvlTreeError::CorruptedPointer { key } => {
+                write!(f, "AvlTree: pointers of node {} are corrupted", key)
+            }
+            AvlTreeError::KeyNotFound { key } => write!(f, "AvlTree: key {} not found", key),
+            AvlTreeError::KeyExists { key } => write!(f, "AvlTree: key {} already exists", key),
+        }
+    }
+}
+
+/// Unwraps the result of an internal operation of the tree and panics with the message of the error,
+/// used by the methods that panic instead of returning the error.
+pub(crate) trait OrPanic<T> {
+    fn or_panic(self) -> T;
+}
+
+impl<T, K: ScryptoSbor + Display> OrPanic<T> for Result<T, AvlTreeError<K>> {
+    fn or_panic(self) -> T {
+        self.unwrap_or_else(|error| panic!("{}", error))
+    }
+}
@@ -3,17 +3,60 @@ use std::hash::Hash;
 
 use scrypto::prelude::*;
 
+use crate::augmented_avl_tree::{Aggregate, AugmentedAvlTree};
+use crate::avl_multi_map::AvlMultiMap;
+use crate::avl_set::AvlSet;
 use crate::avl_tree::AvlTree;
+use crate::interval_tree::IntervalTree;
+use crate::lazy_avl_tree::{LazyAvlTree, LazyUpdate};
+use crate::sequence_tree::SequenceTree;
+use crate::tree_forest::{TreeForest, TreeId};
 
 // Debugging functions
+/// Checks the structure of the tree like `check_structure` and that the keys are sorted ascending.
 pub fn check_health<
     K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
     V: Clone + ScryptoSbor,
 >(
     tree: &mut AvlTree<K, V>,
 ) {
+    let keys = check_structure(tree);
+    if let Some(pair) = keys.windows(2).find(|pair| pair[0] >= pair[1]) {
+        panic!("Key {} is not before key {}.", pair[0], pair[1]);
+    }
+}
+
+/// Checks the parents and balance factors of all nodes, and that the double linked list
+/// connects the nodes in the order of the tree. Returns the keys in the order of the tree.
+/// The keys are not compared, so it also checks trees that are ordered by position.
+fn check_structure<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+>(
+    tree: &mut AvlTree<K, V>,
+) -> Vec<K> {
     let root = tree.root.clone();
-    check_health_recursive(tree, root.as_ref(), true);
+    let mut keys = Vec::new();
+    check_health_recursive(tree, root.as_ref(), true, &mut keys);
+    for (index, key) in keys.iter().enumerate() {
+        let node = tree
+            .get_node(key)
+            .cloned()
+            .expect("Node of subtree should exist.");
+        let prev = index.checked_sub(1).map(|prev| keys[prev].clone());
+        assert_eq!(
+            node.prev, prev,
+            "Previous node of node {} is not correct.",
+            key
+        );
+        assert_eq!(
+            node.next,
+            keys.get(index + 1).cloned(),
+            "Next node of node {} is not correct.",
+            key
+        );
+    }
+    keys
 }
 
 fn check_health_recursive<
@@ -23,6 +66,7 @@ fn check_health_recursive<
     tree: &mut AvlTree<K, V>,
     key: Option<&K>,
     panic: bool,
+    keys: &mut Vec<K>,
 ) -> (i32, Option<K>) {
     if key.is_none() {
         return (0, None);
@@ -34,8 +78,9 @@ fn check_health_recursive<
         .expect("Node of subtree should exist.");
     let left = node.left_child.as_ref();
     let right = node.right_child.as_ref();
-    let (height_left, parent_left) = check_health_recursive(tree, left, panic);
-    let (height_right, parent_right) = check_health_recursive(tree, right, panic);
+    let (height_left, parent_left) = check_health_recursive(tree, left, panic, keys);
+    keys.push(node.key.clone());
+    let (height_right, parent_right) = check_health_recursive(tree, right, panic, keys);
     assert_eq!(
         parent_left,
         node.left_child.as_ref().map(|_| node.key.clone()),
@@ -72,6 +117,126 @@ fn check_health_recursive<
     (height_left.max(height_right) + 1, node.parent.clone())
 }
 
+pub fn check_aggregates<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+    A: Aggregate<K, V> + PartialEq + Debug,
+>(
+    tree: &mut AugmentedAvlTree<K, V, A>,
+) {
+    check_health(tree.inner());
+    check_aggregate_values(tree);
+}
+
+/// Checks the stored aggregates of all nodes, without checking the structure of the tree.
+fn check_aggregate_values<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+    A: Aggregate<K, V> + PartialEq + Debug,
+>(
+    tree: &mut AugmentedAvlTree<K, V, A>,
+) {
+    let root = tree.inner().root.clone();
+    check_aggregates_recursive(tree, root.as_ref());
+}
+
+fn check_aggregates_recursive<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+    A: Aggregate<K, V> + PartialEq + Debug,
+>(
+    tree: &mut AugmentedAvlTree<K, V, A>,
+    key: Option<&K>,
+) -> A {
+    if key.is_none() {
+        return A::empty();
+    }
+    let key = key.unwrap();
+    let node = tree
+        .inner()
+        .store
+        .get(key)
+        .map(|node| node.clone())
+        .expect("Node of subtree should exist.");
+    let left = check_aggregates_recursive(tree, node.left_child.as_ref());
+    let right = check_aggregates_recursive(tree, node.right_child.as_ref());
+    let aggregate = left
+        .combine(&A::from_entry(key, &node.value.value))
+        .combine(&right);
+    assert_eq!(
+        aggregate, node.value.aggregate,
+        "Aggregate of node {} is not correct.",
+        key
+    );
+    aggregate
+}
+
+pub fn check_lazy_health<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+    U: LazyUpdate<V>,
+>(
+    tree: &mut LazyAvlTree<K, V, U>,
+) {
+    check_health(tree.inner());
+}
+
+pub fn check_multi_map_health<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+>(
+    map: &mut AvlMultiMap<K, V>,
+) {
+    check_health(map.inner());
+}
+
+pub fn check_set_health<K: ScryptoSbor + Hash + Ord + Clone + Debug + Display>(
+    set: &mut AvlSet<K>,
+) {
+    check_health(set.inner());
+}
+
+pub fn check_interval_health<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+>(
+    tree: &mut IntervalTree<K, V>,
+) {
+    check_aggregates(tree.inner());
+}
+
+pub fn check_sequence_health<V: Clone + ScryptoSbor>(tree: &mut SequenceTree<V>) {
+    // The ids of a sequence are not sorted, the nodes are ordered by their position.
+    check_structure(tree.inner().inner());
+    check_aggregate_values(tree.inner());
+}
+
+pub fn check_forest_health<
+    K: ScryptoSbor + Hash + Ord + Clone + Debug + Display,
+    V: Clone + ScryptoSbor,
+>(
+    forest: &mut TreeForest<K, V>,
+    id: TreeId,
+) {
+    assert!(
+        forest.nodes.root.is_none(),
+        "Shared tree keeps the root of a closed tree"
+    );
+    let mut tree = forest.tree(id).expect("Tree of the forest not found");
+    let keys: Vec<K> = tree.range(..).map(|(key, _, _)| key).collect();
+    assert_eq!(
+        tree.first(),
+        keys.first().cloned(),
+        "Head of the tree is wrong"
+    );
+    assert_eq!(
+        tree.last(),
+        keys.last().cloned(),
+        "Tail of the tree is wrong"
+    );
+    check_health(tree.inner());
+}
+
 pub fn print_tree_nice<
     K: ScryptoSbor + Debug + Display + Hash + Ord + Clone,
     V: ScryptoSbor + Clone,
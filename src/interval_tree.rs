@@ -0,0 +1,225 @@
+use scrypto::prelude::*;
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds};
+
+use crate::augmented_avl_tree::{Aggregate, AugmentedAvlTree, AugmentedItemRef};
+use crate::avl_tree::{Direction, WithinBound};
+
+/// A half open interval `[lo, hi)`.
+/// Intervals are ordered by `lo` first and by `hi` second.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<K: ScryptoSbor> {
+    lo: K,
+    hi: K,
+}
+
+impl<K: ScryptoSbor> Interval<K> {
+    pub fn lo(&self) -> &K {
+        &self.lo
+    }
+
+    pub fn hi(&self) -> &K {
+        &self.hi
+    }
+}
+
+impl<K: ScryptoSbor + Display> Display for Interval<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.lo, self.hi)
+    }
+}
+
+/// The biggest `hi` of all intervals of a subtree, `None` for an empty subtree.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq)]
+pub struct MaxHi<K: ScryptoSbor>(Option<K>);
+
+impl<K: ScryptoSbor + Clone + Ord, V> Aggregate<Interval<K>, V> for MaxHi<K> {
+    fn empty() -> Self {
+        MaxHi(None)
+    }
+
+    fn from_entry(interval: &Interval<K>, _value: &V) -> Self {
+        MaxHi(Some(interval.hi.clone()))
+    }
+
+    fn combine(&self, right: &Self) -> Self {
+        MaxHi(self.0.clone().max(right.0.clone()))
+    }
+}
+
+/// An `IntervalTree` stores values for half open intervals `[lo, hi)`, e.g. range orders or liquidity positions.
+/// It is an `AugmentedAvlTree` keyed by the intervals, where every subtree stores the biggest `hi` of its intervals.
+/// The aggregate is maintained through the rotations of the tree,
+/// so subtrees without an overlapping interval are skipped by the queries.
+///
+/// Every interval stores one value, inserting the same interval again replaces the value.
+#[derive(ScryptoSbor)]
+pub struct IntervalTree<K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    tree: AugmentedAvlTree<Interval<K>, V, MaxHi<K>>,
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone> Default
+    for IntervalTree<K, V>
+{
+    fn default() -> Self {
+        IntervalTree::new()
+    }
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    IntervalTree<K, V>
+{
+    /// Creates an empty `IntervalTree`.
+    pub fn new() -> Self {
+        IntervalTree {
+            tree: AugmentedAvlTree::new(),
+        }
+    }
+
+    /// Returns the value of the interval `[lo, hi)`.
+    pub fn get(&self, lo: &K, hi: &K) -> Option<AugmentedItemRef<'_, Interval<K>, V, MaxHi<K>>> {
+        self.tree.get(&Self::interval(lo, hi))
+    }
+
+    /// Inserts the value for the interval `[lo, hi)`.
+    /// If the interval already exists the old value is returned and the new value is inserted.
+    ///
+    /// Panics if the interval is empty, i.e. `lo >= hi`.
+    pub fn insert(&mut self, lo: K, hi: K, value: V) -> Option<V> {
+        assert!(lo < hi, "Interval has to be non empty: {} >= {}", lo, hi);
+        self.tree.insert(Interval { lo, hi }, value)
+    }
+
+    /// Deletes the interval `[lo, hi)` from the tree.
+    /// Returns the value of the deleted interval if it existed.
+    pub fn remove(&mut self, lo: &K, hi: &K) -> Option<V> {
+        self.tree.remove(&Self::interval(lo, hi))
+    }
+
+    /// Iterates over all intervals that contain the point, in order of the intervals.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert(1, 5, "a");
+    /// tree.insert(3, 4, "b");
+    /// tree.insert(4, 9, "c");
+    /// for (interval, value) in tree.stab(&4) {
+    ///     println!("{} {}", interval, value);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// [1, 5) a, [4, 9) c
+    /// ```
+    pub fn stab(&self, point: &K) -> IntervalIterator<'_, K, V> {
+        self.overlapping(point.clone()..=point.clone())
+    }
+
+    /// Iterates over all intervals that overlap the range, in order of the intervals.
+    /// An interval `[lo, hi)` overlaps the range if `lo` is before the end of the range and `hi` is after the start of the range.
+    ///
+    /// Example:
+    /// ```
+    /// tree.insert(1, 5, "a");
+    /// tree.insert(3, 4, "b");
+    /// tree.insert(6, 9, "c");
+    /// for (interval, value) in tree.overlapping(4..6) {
+    ///     println!("{} {}", interval, value);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// [1, 5) a
+    /// ```
+    pub fn overlapping<R: RangeBounds<K>>(&self, range: R) -> IntervalIterator<'_, K, V> {
+        let mut iterator = IntervalIterator {
+            tree: &self.tree,
+            stack: Vec::new(),
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+        };
+        iterator.push_left_path(self.tree.root());
+        iterator
+    }
+
+    // PRIVATE METHODS
+
+    /// Return the inner tree, public in crate for the health checking.
+    pub(crate) fn inner(&mut self) -> &mut AugmentedAvlTree<Interval<K>, V, MaxHi<K>> {
+        &mut self.tree
+    }
+
+    fn interval(lo: &K, hi: &K) -> Interval<K> {
+        Interval {
+            lo: lo.clone(),
+            hi: hi.clone(),
+        }
+    }
+}
+
+/// `IntervalIterator` traverses the tree in order and yields the intervals that overlap a range.
+///
+/// Subtrees whose biggest `hi` is not after the start of the range are skipped,
+/// as well as all intervals whose `lo` is after the end of the range.
+pub struct IntervalIterator<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    tree: &'a AugmentedAvlTree<Interval<K>, V, MaxHi<K>>,
+    /// The nodes whose right subtree is not visited yet, the next node on top.
+    stack: Vec<Interval<K>>,
+    start: Bound<K>,
+    end: Bound<K>,
+}
+
+impl<'a, K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    IntervalIterator<'a, K, V>
+{
+    /// Pushes the nodes from the given subtree root down to its leftmost node that can overlap the range.
+    fn push_left_path(&mut self, mut current: Option<Interval<K>>) {
+        while let Some(interval) = current {
+            let node = self.tree.node(&interval);
+            if !self.after_start(node.value.aggregate.0.as_ref()) {
+                // No interval of the subtree ends after the start of the range.
+                return;
+            }
+            if self
+                .end
+                .as_ref()
+                .within_bound(&interval.lo, Direction::Right)
+            {
+                self.stack.push(interval);
+            }
+            // Otherwise the node and its right subtree start after the end of the range.
+            current = node.left_child.clone();
+        }
+    }
+
+    /// Returns true if `hi` is after the start of the range.
+    fn after_start(&self, hi: Option<&K>) -> bool {
+        match (&self.start, hi) {
+            (_, None) => false,
+            (Bound::Unbounded, Some(_)) => true,
+            (Bound::Included(start) | Bound::Excluded(start), Some(hi)) => hi > start,
+        }
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    Iterator for IntervalIterator<'a, K, V>
+{
+    type Item = (Interval<K>, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(interval) = self.stack.pop() {
+            let node = self.tree.node(&interval);
+            let value = node.value.value.clone();
+            let right_child = node.right_child.clone();
+            drop(node);
+            self.push_left_path(right_child);
+            if self.after_start(Some(&interval.hi)) {
+                return Some((interval, value));
+            }
+        }
+        None
+    }
+}
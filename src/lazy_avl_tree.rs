@@ -0,0 +1,436 @@
+use scrypto::prelude::*;
+use std::hash::Hash;
+use std::mem;
+use std::ops::{Bound, Deref, DerefMut, RangeBounds};
+
+use crate::avl_tree::{AvlTree, Direction, ItemRefMut, Node, NodeIterator, WithinBound};
+
+/// An update that can be applied to many values at once, e.g. adding a delta.
+pub trait LazyUpdate<V>: ScryptoSbor + Clone {
+    /// Applies the update to a value.
+    fn apply(&self, value: &mut V);
+    /// Returns the update with the same effect as applying `self` and afterwards `newer`.
+    fn compose(&self, newer: &Self) -> Self;
+}
+
+/// A `LazyAvlTree` is an `AvlTree` where an update can be applied to all values of a range in `O(log n)`.
+///
+/// Instead of writing every value of the range, the update is stored as a pending tag on the
+/// roots of the subtrees that are completely inside the range. The value of a node is always
+/// up to date with the pending tags of the node itself, the tags of the node apply to all nodes below it.
+/// So the actual value of a node is its stored value with the pending tags of all its ancestors applied.
+///
+/// Before the tree is restructured, the tags of all nodes that are moved by the insert, the delete
+/// rewiring or a rotation are pushed down to their children, so a tag never applies to other nodes
+/// than the ones of its subtree at the time it was set.
+#[derive(ScryptoSbor)]
+pub struct LazyAvlTree<K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor, U: ScryptoSbor> {
+    tree: AvlTree<K, LazyValue<V, U>>,
+}
+
+/// The value stored in the inner tree, the value of the user with the pending update for the subtree below.
+#[derive(ScryptoSbor, Clone)]
+pub(crate) struct LazyValue<V: ScryptoSbor, U: ScryptoSbor> {
+    pub(crate) value: V,
+    pub(crate) pending: Option<U>,
+}
+
+impl<
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        U: LazyUpdate<V>,
+    > Default for LazyAvlTree<K, V, U>
+{
+    fn default() -> Self {
+        LazyAvlTree::new()
+    }
+}
+
+impl<
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        U: LazyUpdate<V>,
+    > LazyAvlTree<K, V, U>
+{
+    /// Creates an empty `LazyAvlTree`.
+    pub fn new() -> Self {
+        LazyAvlTree {
+            tree: AvlTree::new(),
+        }
+    }
+
+    /// Returns the actual value of the given key.
+    /// The pending updates of the ancestors are applied to a copy of the value, so nothing is written.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut pending = Vec::new();
+        let mut current = self.tree.root.clone();
+        while let Some(current_key) = current {
+            let node = self.node(&current_key);
+            if current_key == *key {
+                let mut value = node.value.value.clone();
+                // The deepest pending update is the oldest one.
+                for update in pending.iter().rev() {
+                    U::apply(update, &mut value);
+                }
+                return Some(value);
+            }
+            pending.extend(node.value.pending.clone());
+            current = match *key < current_key {
+                true => node.left_child.clone(),
+                false => node.right_child.clone(),
+            };
+        }
+        None
+    }
+
+    /// Returns the value of the given key in a mutable wrapper, that writes back to the tree on drop.
+    /// The pending updates on the path to the key are pushed down first, so the stored value is the actual value.
+    pub fn get_mut(&mut self, key: &K) -> Option<LazyItemRefMut<'_, K, V, U>> {
+        self.push_down_path(key);
+        self.tree.get_mut(key).map(|item| LazyItemRefMut { item })
+    }
+
+    /// Inserts a new key value pair into the tree.
+    /// If the key already exists the old value is returned and the new value is inserted.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.push_down_path(&key);
+        if let Some(mut existing) = self.tree.get_mut(&key) {
+            return Some(mem::replace(&mut existing.value, value));
+        }
+        self.tree.insert(
+            key,
+            LazyValue {
+                value,
+                pending: None,
+            },
+        );
+        None
+    }
+
+    /// Deletes the given key from the tree.
+    /// Returns the value of the deleted key if it existed.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.push_down_for_remove(key);
+        self.tree.remove(key).map(|removed| removed.value)
+    }
+
+    /// Applies the update to all values inside the range.
+    /// Only the nodes on the paths to both ends of the range and the roots of the subtrees in between are written,
+    /// so the update needs `O(log n)` accesses to the KVStore instead of one per value.
+    ///
+    /// Example:
+    ///
+    /// Tree is initialized with all integers from 0 to 100 and value = key, the update adds a delta.
+    /// ```
+    /// tree.range_apply(10..15, Add(100));
+    /// for (k, v, _) in tree.range(8..17) {
+    ///     println!("{}", v);
+    /// }
+    /// ```
+    ///
+    /// Gives:
+    /// ```
+    /// 8, 9, 110, 111, 112, 113, 114, 15, 16
+    /// ```
+    pub fn range_apply<R: RangeBounds<K>>(&mut self, range: R, update: U) {
+        if let Some(root) = self.tree.root.clone() {
+            self.apply_subtree(
+                root,
+                (None, None),
+                (range.start_bound(), range.end_bound()),
+                &update,
+            );
+        }
+    }
+
+    /// Iterates over the actual values in order of the keys, see `AvlTree::range`.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> LazyNodeIterator<'_, K, V, U> {
+        LazyNodeIterator {
+            nodes: self.tree.range(range),
+            tree: &self.tree,
+            pending_below: HashMap::new(),
+        }
+    }
+
+    /// Iterates backwards over the actual values, see `AvlTree::range_back`.
+    pub fn range_back<R: RangeBounds<K>>(&self, range: R) -> LazyNodeIterator<'_, K, V, U> {
+        LazyNodeIterator {
+            nodes: self.tree.range_back(range),
+            tree: &self.tree,
+            pending_below: HashMap::new(),
+        }
+    }
+
+    // PRIVATE METHODS
+
+    /// Return the inner tree, public in crate for the health checking.
+    pub(crate) fn inner(&mut self) -> &mut AvlTree<K, LazyValue<V, U>> {
+        &mut self.tree
+    }
+
+    fn node(&self, key: &K) -> KeyValueEntryRef<'_, Node<K, LazyValue<V, U>>> {
+        self.tree
+            .store
+            .get(key)
+            .expect("Node of subtree should exist.")
+    }
+
+    /// Applies the update to the subtree of the given node.
+    /// The subtree contains only keys between the exclusive bounds given by its ancestors.
+    fn apply_subtree(
+        &mut self,
+        key: K,
+        (lower, upper): (Option<K>, Option<K>),
+        (start, end): (Bound<&K>, Bound<&K>),
+        update: &U,
+    ) {
+        let contains_subtree = lower
+            .as_ref()
+            .map_or(matches!(start, Bound::Unbounded), |lower| {
+                start.within_bound(lower, Direction::Left)
+            })
+            && upper
+                .as_ref()
+                .map_or(matches!(end, Bound::Unbounded), |upper| {
+                    end.within_bound(upper, Direction::Right)
+                });
+        if contains_subtree {
+            self.apply_to_node(&key, update);
+            return;
+        }
+        let contains_key =
+            start.within_bound(&key, Direction::Left) && end.within_bound(&key, Direction::Right);
+        let (left, right) = self.push_down(&key, contains_key.then_some(update));
+        let start_is_left = match start {
+            Bound::Included(start) | Bound::Excluded(start) => *start < key,
+            Bound::Unbounded => true,
+        };
+        let end_is_right = match end {
+            Bound::Included(end) | Bound::Excluded(end) => *end > key,
+            Bound::Unbounded => true,
+        };
+        if let Some(left) = left.filter(|_| start_is_left) {
+            self.apply_subtree(left, (lower, Some(key.clone())), (start, end), update);
+        }
+        if let Some(right) = right.filter(|_| end_is_right) {
+            self.apply_subtree(right, (Some(key), upper), (start, end), update);
+        }
+    }
+
+    /// Applies the update to the value of the node and adds it to the pending update of its subtree.
+    fn apply_to_node(&mut self, key: &K, update: &U) {
+        let mut node = self.tree.store.get_mut(key).expect("Node not found");
+        update.apply(&mut node.value.value);
+        node.value.pending = Some(match node.value.pending.take() {
+            Some(pending) => pending.compose(update),
+            None => update.clone(),
+        });
+    }
+
+    /// Pushes the pending update of the node down to its children and optionally applies an update to the node value.
+    /// Returns the children of the node.
+    fn push_down(&mut self, key: &K, update_value: Option<&U>) -> (Option<K>, Option<K>) {
+        let (pending, left, right) = {
+            let node = self.node(key);
+            let children = (node.left_child.clone(), node.right_child.clone());
+            (node.value.pending.clone(), children.0, children.1)
+        };
+        if pending.is_some() || update_value.is_some() {
+            let mut node = self.tree.store.get_mut(key).expect("Node not found");
+            node.value.pending = None;
+            if let Some(update) = update_value {
+                update.apply(&mut node.value.value);
+            }
+        }
+        if let Some(pending) = pending {
+            for child in left.iter().chain(right.iter()) {
+                self.apply_to_node(child, &pending);
+            }
+        }
+        (left, right)
+    }
+
+    /// Pushes down the pending updates of all nodes on the path to the key, excluding the node of the key.
+    /// Afterwards the stored value of the key is the actual value and a new node can be added below the path.
+    fn push_down_path(&mut self, key: &K) {
+        let mut current = self.tree.root.clone();
+        while let Some(current_key) = current {
+            if current_key == *key {
+                return;
+            }
+            let (left, right) = self.push_down(&current_key, None);
+            current = match *key < current_key {
+                true => left,
+                false => right,
+            };
+        }
+    }
+
+    /// Pushes down the pending updates of all nodes that can be moved by deleting the key.
+    /// These are the nodes on the path to the key and to the node that replaces it, the siblings
+    /// of the path that can be rotated upwards, and their children towards the path.
+    fn push_down_for_remove(&mut self, key: &K) {
+        // The nodes of the path with the sibling of the path below them.
+        let mut path = Vec::new();
+        let mut found = false;
+        // Whether the replacing node is left of the deleted node, known once the deleted node is found.
+        let mut replace_left: Option<bool> = None;
+        let mut current = self.tree.root.clone();
+        while let Some(current_key) = current {
+            let (left, right) = self.push_down(&current_key, None);
+            let go_left = match (replace_left, current_key == *key) {
+                // Below the first step the replacing node is the outermost node towards the deleted node.
+                (Some(replace_left), _) => !replace_left,
+                (None, true) => {
+                    found = true;
+                    if left.is_none() || right.is_none() {
+                        break;
+                    }
+                    // Like `calculate_replace_node`: a left heavy node is replaced by its predecessor,
+                    // any other node by its successor.
+                    let left_heavy = self.node(&current_key).balance_factor < 0;
+                    replace_left = Some(left_heavy);
+                    left_heavy
+                }
+                (None, false) => *key < current_key,
+            };
+            let (next, sibling) = match go_left {
+                true => (left, right),
+                false => (right, left),
+            };
+            if next.is_none() {
+                break;
+            }
+            path.push((current_key, sibling));
+            current = next;
+        }
+        if !found {
+            return;
+        }
+        for (parent, sibling) in path {
+            let Some(sibling) = sibling else {
+                continue;
+            };
+            let (sibling_left, sibling_right) = self.push_down(&sibling, None);
+            let inner = match sibling < parent {
+                true => sibling_right,
+                false => sibling_left,
+            };
+            if let Some(inner) = inner {
+                self.push_down(&inner, None);
+            }
+        }
+    }
+}
+
+/// `LazyNodeIterator` iterates over a range like `NodeIterator` and yields the actual values.
+/// The pending updates of the ancestors are collected once per ancestor and applied to a copy of the value.
+pub struct LazyNodeIterator<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor, U: ScryptoSbor> {
+    nodes: NodeIterator<'a, K, LazyValue<V, U>>,
+    tree: &'a AvlTree<K, LazyValue<V, U>>,
+    /// The composed pending updates of a node and all its ancestors, which apply to the children of the node.
+    pending_below: HashMap<K, Option<U>>,
+}
+
+impl<
+        'a,
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        U: LazyUpdate<V>,
+    > LazyNodeIterator<'a, K, V, U>
+{
+    pub fn has_next(&self) -> bool {
+        self.nodes.has_next()
+    }
+
+    pub fn next_key(&self) -> Option<K> {
+        self.nodes.next_key()
+    }
+
+    /// Returns the key, the actual value and the next key of a node yielded by the inner iterator.
+    fn actual_value(
+        &mut self,
+        node: KeyValueEntryRef<'a, Node<K, LazyValue<V, U>>>,
+        next_key: Option<K>,
+    ) -> (K, V, Option<K>) {
+        let mut value = node.value.value.clone();
+        if let Some(update) = self.pending_below(node.parent.clone()) {
+            update.apply(&mut value);
+        }
+        (node.key.clone(), value, next_key)
+    }
+
+    /// Returns the composed pending updates of the given node and all its ancestors.
+    fn pending_below(&mut self, parent: Option<K>) -> Option<U> {
+        // Go up until a node is reached, whose pending updates below are already known.
+        let mut unknown = Vec::new();
+        let mut current = parent;
+        let mut below = None;
+        while let Some(key) = current {
+            if let Some(known) = self.pending_below.get(&key) {
+                below = known.clone();
+                break;
+            }
+            let node = self.tree.store.get(&key).expect("Node not found");
+            current = node.parent.clone();
+            unknown.push((key, node.value.pending.clone()));
+        }
+        // Go down again, the pending update of a node is older than the ones of its ancestors.
+        for (key, pending) in unknown.into_iter().rev() {
+            below = match (pending, below) {
+                (Some(pending), Some(below)) => Some(pending.compose(&below)),
+                (pending, below) => pending.or(below),
+            };
+            self.pending_below.insert(key, below.clone());
+        }
+        below
+    }
+}
+
+impl<
+        'a,
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        U: LazyUpdate<V>,
+    > Iterator for LazyNodeIterator<'a, K, V, U>
+{
+    type Item = (K, V, Option<K>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.next_node()?;
+        let next_key = self.nodes.next_key();
+        Some(self.actual_value(node, next_key))
+    }
+}
+
+impl<
+        'a,
+        K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug,
+        V: ScryptoSbor + Clone,
+        U: LazyUpdate<V>,
+    > DoubleEndedIterator for LazyNodeIterator<'a, K, V, U>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.next_back_node()?;
+        let next_key = self.nodes.next_back_key();
+        Some(self.actual_value(node, next_key))
+    }
+}
+
+pub struct LazyItemRefMut<'a, K: ScryptoSbor, V: ScryptoSbor, U: ScryptoSbor> {
+    item: ItemRefMut<'a, K, LazyValue<V, U>>,
+}
+
+impl<'a, K: ScryptoSbor, V: ScryptoSbor, U: ScryptoSbor> Deref for LazyItemRefMut<'a, K, V, U> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item.value
+    }
+}
+
+impl<'a, K: ScryptoSbor, V: ScryptoSbor, U: ScryptoSbor> DerefMut for LazyItemRefMut<'a, K, V, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item.value
+    }
+}
@@ -1,4 +1,24 @@
+pub mod augmented_avl_tree;
+pub mod avl_multi_map;
+pub mod avl_set;
+pub mod avl_set_operations;
 pub mod avl_tree;
+pub mod avl_tree_error;
 pub mod avl_tree_health;
+pub mod interval_tree;
+pub mod lazy_avl_tree;
+pub mod merge_iter;
+pub mod sequence_tree;
+pub mod tree_forest;
 
+pub use augmented_avl_tree::*;
+pub use avl_multi_map::*;
+pub use avl_set::*;
+pub use avl_set_operations::*;
 pub use avl_tree::*;
+pub use avl_tree_error::*;
+pub use interval_tree::*;
+pub use lazy_avl_tree::*;
+pub use merge_iter::*;
+pub use sequence_tree::*;
+pub use tree_forest::*;
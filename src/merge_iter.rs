@@ -0,0 +1,102 @@
+use scrypto::prelude::*;
+use std::hash::Hash;
+use std::ops::RangeBounds;
+
+use crate::avl_tree::{AvlTree, Direction, NodeIterator};
+
+/// `MergeIter` merges the iterators of multiple trees into a single iteration in key order.
+/// Every entry is yielded with the index of the iterator it comes from, equal keys are yielded in order of the index.
+///
+/// The next entry of every iterator is kept and the smallest, or biggest for a backwards iteration, is yielded next.
+/// Every step compares the next entries of all iterators, which is meant for a small number of trees.
+pub struct MergeIter<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    iterators: Vec<NodeIterator<'a, K, V>>,
+    heads: Vec<Option<(K, V)>>,
+    direction: Direction,
+}
+
+impl<'a, K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    MergeIter<'a, K, V>
+{
+    /// Merges the given iterators, which all have to iterate in the same direction.
+    ///
+    /// Example:
+    /// ```
+    /// // tree_a = {1: a, 4: b}, tree_b = {2: c, 4: d}
+    /// let merged = MergeIter::new(vec![tree_a.range(..), tree_b.range(..)]);
+    /// assert_eq!(merged.collect::<Vec<_>>(), vec![(0, 1, a), (1, 2, c), (0, 4, b), (1, 4, d)]);
+    /// ```
+    pub fn new(mut iterators: Vec<NodeIterator<'a, K, V>>) -> Self {
+        let direction = iterators
+            .first()
+            .map(|iterator| iterator.direction())
+            .unwrap_or(Direction::Right);
+        assert!(
+            iterators
+                .iter()
+                .all(|iterator| iterator.direction() == direction),
+            "All merged iterators have to iterate in the same direction"
+        );
+        let heads = iterators
+            .iter_mut()
+            .map(|iterator| iterator.next().map(|(key, value, _)| (key, value)))
+            .collect();
+        MergeIter {
+            iterators,
+            heads,
+            direction,
+        }
+    }
+
+    /// Merges the same range of all trees in ascending order of the keys.
+    pub fn range<R: RangeBounds<K> + Clone>(trees: &[&'a AvlTree<K, V>], range: R) -> Self {
+        Self::new(trees.iter().map(|tree| tree.range(range.clone())).collect())
+    }
+
+    /// Merges the same range of all trees in descending order of the keys.
+    pub fn range_back<R: RangeBounds<K> + Clone>(trees: &[&'a AvlTree<K, V>], range: R) -> Self {
+        Self::new(
+            trees
+                .iter()
+                .map(|tree| tree.range_back(range.clone()))
+                .collect(),
+        )
+    }
+
+    /// Returns the index of the iterator with the next entry.
+    fn next_index(&self) -> Option<usize> {
+        let mut next: Option<(usize, &K)> = None;
+        for (index, head) in self.heads.iter().enumerate() {
+            let Some((key, _)) = head else {
+                continue;
+            };
+            let is_next = match next {
+                None => true,
+                Some((_, next_key)) => match self.direction {
+                    Direction::Right => key < next_key,
+                    Direction::Left => key > next_key,
+                },
+            };
+            if is_next {
+                next = Some((index, key));
+            }
+        }
+        next.map(|(index, _)| index)
+    }
+}
+
+impl<'a, K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    Iterator for MergeIter<'a, K, V>
+{
+    type Item = (usize, K, V);
+
+    /// Returns the next entry with the index of its iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next_index()?;
+        let next_head = self.iterators[index]
+            .next()
+            .map(|(key, value, _)| (key, value));
+        let (key, value) = mem::replace(&mut self.heads[index], next_head)?;
+        Some((index, key, value))
+    }
+}
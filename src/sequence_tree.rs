@@ -0,0 +1,157 @@
+use scrypto::prelude::*;
+use std::cmp::Ordering::{Equal, Greater, Less};
+
+use crate::augmented_avl_tree::{Aggregate, AugmentedAvlTree, AugmentedItemRef};
+use crate::avl_tree::Direction;
+
+/// The number of entries of a subtree.
+#[derive(ScryptoSbor, Clone, Debug, PartialEq)]
+pub struct Size(u64);
+
+impl<V> Aggregate<u64, V> for Size {
+    fn empty() -> Self {
+        Size(0)
+    }
+
+    fn from_entry(_id: &u64, _value: &V) -> Self {
+        Size(1)
+    }
+
+    fn combine(&self, right: &Self) -> Self {
+        Size(self.0 + right.0)
+    }
+}
+
+/// A `SequenceTree` is a list whose entries are accessed by their index, like a `Vec` stored in a KVStore.
+/// Inserting and removing at any index needs `O(log n)` without renumbering the following entries.
+///
+/// It is an `AugmentedAvlTree` where every subtree stores its size. The nodes are ordered by their position
+/// instead of their key: every node gets a fixed id, which is never compared, and the index of a node
+/// is found by descending from the root with the sizes of the left subtrees.
+#[derive(ScryptoSbor)]
+pub struct SequenceTree<V: ScryptoSbor> {
+    tree: AugmentedAvlTree<u64, V, Size>,
+    /// The id of the next inserted node.
+    next_id: u64,
+}
+
+impl<V: ScryptoSbor + Clone> Default for SequenceTree<V> {
+    fn default() -> Self {
+        SequenceTree::new()
+    }
+}
+
+impl<V: ScryptoSbor + Clone> SequenceTree<V> {
+    /// Creates an empty `SequenceTree`.
+    pub fn new() -> Self {
+        SequenceTree {
+            tree: AugmentedAvlTree::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Returns the number of entries in `O(1)`.
+    pub fn len(&self) -> u64 {
+        self.tree.total().0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at the index in a AugmentedItemRef.
+    pub fn get(&self, index: u64) -> Option<AugmentedItemRef<'_, u64, V, Size>> {
+        let id = self.id_at(index)?;
+        self.tree.get(&id)
+    }
+
+    /// Changes the value at the index with the function.
+    /// Returns the result of the function or None if the index is out of bounds.
+    pub fn update<R>(&mut self, index: u64, function: impl FnOnce(&mut V) -> R) -> Option<R> {
+        let id = self.id_at(index)?;
+        self.tree.update(&id, function)
+    }
+
+    /// Inserts the value at the index, the entries from the index on move one position back.
+    ///
+    /// Panics if the index is bigger than the length.
+    ///
+    /// Example:
+    /// ```
+    /// list.push_back("a");
+    /// list.push_back("c");
+    /// list.insert_at(1, "b");
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    /// ```
+    pub fn insert_at(&mut self, index: u64, value: V) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "Index {} is out of bounds for length {}",
+            index,
+            len
+        );
+        let id = self.next_id;
+        self.next_id += 1;
+        // Insert before the entry at the index, or after the last entry to append.
+        let (neighbour, direction) = match index < len {
+            true => (self.id_at(index), Direction::Left),
+            false => (
+                len.checked_sub(1).and_then(|last| self.id_at(last)),
+                Direction::Right,
+            ),
+        };
+        self.tree
+            .insert_beside(neighbour.as_ref(), direction, id, value);
+    }
+
+    /// Appends the value after the last entry.
+    pub fn push_back(&mut self, value: V) {
+        self.insert_at(self.len(), value);
+    }
+
+    /// Inserts the value before the first entry.
+    pub fn push_front(&mut self, value: V) {
+        self.insert_at(0, value);
+    }
+
+    /// Removes the value at the index, the following entries move one position forward.
+    /// Returns the removed value or None if the index is out of bounds.
+    pub fn remove_at(&mut self, index: u64) -> Option<V> {
+        let id = self.id_at(index)?;
+        self.tree.remove(&id)
+    }
+
+    /// Iterates over the values in order of their index.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = V> + '_ {
+        self.tree.range(..).map(|(_, value, _)| value)
+    }
+
+    // PRIVATE METHODS
+
+    /// Return the inner tree, public in crate for the health checking.
+    pub(crate) fn inner(&mut self) -> &mut AugmentedAvlTree<u64, V, Size> {
+        &mut self.tree
+    }
+
+    /// Returns the id of the node at the index by descending from the root with the sizes of the subtrees.
+    fn id_at(&self, mut index: u64) -> Option<u64> {
+        let mut current = self.tree.root();
+        while let Some(id) = current {
+            let node = self.tree.node(&id);
+            let left_size = node
+                .left_child
+                .as_ref()
+                .map_or(0, |left| self.tree.node(left).value.aggregate.0);
+            match index.cmp(&left_size) {
+                Less => current = node.left_child,
+                Equal => return Some(id),
+                Greater => {
+                    index -= left_size + 1;
+                    current = node.right_child;
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,409 @@
+use scrypto::prelude::*;
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds};
+
+use crate::avl_tree::{AvlTree, Direction, ItemRef, ItemRefMut};
+use crate::avl_tree_error::{AvlTreeError, OrPanic};
+
+/// The id of a tree in a `TreeForest`, handed out by `TreeForest::create_tree`.
+pub type TreeId = u64;
+
+/// A `TreeForest` keeps many logical `AvlTree`s in one shared KVStore, e.g. one tree per market or per user.
+/// Every tree only owns its root and its smallest and biggest key, the nodes of all trees live in the same store.
+///
+/// The trees share one key space: the nodes are stored under their key, so a key is in at most one tree
+/// of the forest, e.g. the ids of orders that are kept in the tree of their market.
+/// Because the tree is not part of the key, entries are moved between trees by changing the pointers
+/// of the nodes, the stored values are never copied.
+#[derive(ScryptoSbor)]
+pub struct TreeForest<K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    /// The shared tree of all nodes, its root is only set while a handle is open.
+    pub(crate) nodes: AvlTree<K, V>,
+    trees: KeyValueStore<TreeId, TreeState<K>>,
+    next_tree: TreeId,
+}
+
+/// The state of a single tree of the forest.
+/// - `root`: The root node of the tree in the shared store.
+/// - `head`: The smallest key of the tree.
+/// - `tail`: The biggest key of the tree.
+#[derive(ScryptoSbor, Clone, Debug)]
+pub(crate) struct TreeState<K: ScryptoSbor> {
+    root: Option<K>,
+    head: Option<K>,
+    tail: Option<K>,
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone> Default
+    for TreeForest<K, V>
+{
+    fn default() -> Self {
+        TreeForest::new()
+    }
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    TreeForest<K, V>
+{
+    /// Creates an empty `TreeForest`.
+    pub fn new() -> Self {
+        TreeForest {
+            nodes: AvlTree::new(),
+            trees: KeyValueStore::new(),
+            next_tree: 0,
+        }
+    }
+
+    /// Adds an empty tree to the forest and returns its id.
+    pub fn create_tree(&mut self) -> TreeId {
+        let id = self.next_tree;
+        self.next_tree += 1;
+        self.trees.insert(
+            id,
+            TreeState {
+                root: None,
+                head: None,
+                tail: None,
+            },
+        );
+        id
+    }
+
+    /// Returns the handle of the tree, `None` if the forest has no tree with the id.
+    /// The state of the tree is written back when the handle is dropped.
+    ///
+    /// Example:
+    /// ```
+    /// let market = forest.create_tree();
+    /// forest.tree(market).unwrap().insert(order_id, order);
+    /// ```
+    pub fn tree(&mut self, id: TreeId) -> Option<TreeHandle<'_, K, V>> {
+        let state = self.trees.get_mut(&id)?;
+        Some(TreeHandle {
+            id,
+            state,
+            nodes: &mut self.nodes,
+        })
+    }
+
+    /// Moves the entry of the key from one tree to another.
+    /// The node is unlinked from the source tree and linked into the target tree, like a `remove` and an `insert`
+    /// without reading or writing the value.
+    ///
+    /// Returns `KeyNotFound` if the source tree does not contain the key, then nothing is changed.
+    ///
+    /// Panics if one of the trees does not exist.
+    pub fn move_entry(&mut self, from: TreeId, to: TreeId, key: &K) -> Result<(), AvlTreeError<K>> {
+        self.assert_tree_exists(to);
+        if !self.existing_tree(from).unlink(key) {
+            return Err(AvlTreeError::KeyNotFound { key: key.clone() });
+        }
+        self.existing_tree(to).link(key.clone());
+        Ok(())
+    }
+
+    /// Moves all entries of the range from one tree to another,
+    /// e.g. to split a tree with `move_range(from, forest.create_tree(), key..)` or to merge two trees with `move_range(from, to, ..)`.
+    /// The range is cut out of the source tree like in `AvlTree::drain_range`, but its nodes stay in the shared store.
+    /// If the moved keys are all bigger or all smaller than the keys of the target tree, the cut tree is joined
+    /// with the target tree in `O(log n)`, so splitting and merging trees does not depend on the number of moved entries.
+    /// Otherwise every moved node is linked into the target tree one by one.
+    ///
+    /// Panics if one of the trees does not exist or both ids are the same.
+    pub fn move_range<R: RangeBounds<K>>(&mut self, from: TreeId, to: TreeId, range: R) {
+        assert_ne!(
+            from, to,
+            "TreeForest: can not move a range into its own tree"
+        );
+        self.assert_tree_exists(to);
+        let Some((first, last, cut_root)) = self.existing_tree(from).cut_range(range) else {
+            return;
+        };
+        self.existing_tree(to).attach(first, last, cut_root);
+    }
+
+    // PRIVATE METHODS
+
+    /// Panics if the tree does not exist, without opening its handle.
+    fn assert_tree_exists(&self, id: TreeId) {
+        assert!(
+            self.trees.get(&id).is_some(),
+            "TreeForest: tree {} does not exist",
+            id
+        );
+    }
+
+    /// Returns the handle of the tree and panics if the tree does not exist.
+    fn existing_tree(&mut self, id: TreeId) -> TreeHandle<'_, K, V> {
+        self.tree(id)
+            .unwrap_or_else(|| panic!("TreeForest: tree {} does not exist", id))
+    }
+}
+
+/// The handle of a single tree of a `TreeForest`, it offers the map operations of an `AvlTree` on that tree.
+/// - `id`: The id of the tree.
+/// - `state`: The open state of the tree, written back to the forest on drop.
+/// - `nodes`: The shared tree of all nodes of the forest.
+///
+/// The shared store contains the keys of all trees, so every lookup of a key searches it from the root of this tree
+/// and needs `O(log n)` accesses to the shared store.
+pub struct TreeHandle<'a, K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> {
+    id: TreeId,
+    state: KeyValueEntryRefMut<'a, TreeState<K>>,
+    nodes: &'a mut AvlTree<K, V>,
+}
+
+impl<K: ScryptoSbor + Clone + Display + Eq + Ord + Hash + Debug, V: ScryptoSbor + Clone>
+    TreeHandle<'_, K, V>
+{
+    /// Returns the id of the tree.
+    pub fn id(&self) -> TreeId {
+        self.id
+    }
+
+    /// Returns `true` if the tree has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.state.root.is_none()
+    }
+
+    /// Returns the smallest key of the tree without accessing the shared store.
+    pub fn first(&self) -> Option<K> {
+        self.state.head.clone()
+    }
+
+    /// Returns the biggest key of the tree without accessing the shared store.
+    pub fn last(&self) -> Option<K> {
+        self.state.tail.clone()
+    }
+
+    /// Returns `true` if the key is in this tree.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.nodes
+            .find_first_node(
+                self.state.root.clone(),
+                Bound::Included(key),
+                Direction::Right,
+            )
+            .or_panic()
+            .as_ref()
+            == Some(key)
+    }
+
+    /// Returns the value of the key in this tree.
+    pub fn get(&self, key: &K) -> Option<ItemRef<'_, K, V>> {
+        match self.contains_key(key) {
+            true => self.nodes.get(key),
+            false => None,
+        }
+    }
+
+    /// Returns the mutable value of the key in this tree.
+    pub fn get_mut(&mut self, key: &K) -> Option<ItemRefMut<'_, K, V>> {
+        match self.contains_key(key) {
+            true => self.nodes.get_mut(key),
+            false => None,
+        }
+    }
+
+    /// Inserts the key value pair into this tree, returns the old value if the key was already present.
+    ///
+    /// Panics if the key is in another tree of the forest.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.assert_not_in_other_tree(&key);
+        let old_value = self.inner().insert(key.clone(), value);
+        self.state.root = self.nodes.root.clone();
+        if self.state.head.as_ref().map_or(true, |head| key < *head) {
+            self.state.head = Some(key.clone());
+        }
+        if self.state.tail.as_ref().map_or(true, |tail| key > *tail) {
+            self.state.tail = Some(key);
+        }
+        old_value
+    }
+
+    /// Removes the key from this tree and returns its value.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let value = self.inner().remove(key)?;
+        self.state.root = self.nodes.root.clone();
+        self.refresh_end_of(key);
+        Some(value)
+    }
+
+    /// Inserts a batch of entries sorted ascending by key into this tree, see `AvlTree::insert_sorted_batch`.
+    ///
+    /// Panics if a key of the batch is in another tree of the forest.
+    pub fn insert_sorted_batch<I: IntoIterator<Item = (K, V)>>(&mut self, entries: I) {
+        let entries: Vec<(K, V)> = entries.into_iter().collect();
+        for (key, _) in entries.iter() {
+            self.assert_not_in_other_tree(key);
+        }
+        self.inner().insert_sorted_batch(entries);
+        self.refresh_ends();
+    }
+
+    /// Removes all entries in the range from this tree and returns them in ascending order, see `AvlTree::drain_range`.
+    pub fn drain_range<R: RangeBounds<K>>(&mut self, range: R) -> Vec<(K, V)> {
+        let Some((first, last)) = self.own_range(range) else {
+            return Vec::new();
+        };
+        let entries = self.inner().drain_range(first..=last);
+        self.refresh_ends();
+        entries
+    }
+
+    /// Iterates over the entries of the range in this tree in ascending order.
+    pub fn range<R: RangeBounds<K>>(
+        &mut self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (K, V, Option<K>)> + '_ {
+        let own_range = self.own_range(range);
+        let nodes: &AvlTree<K, V> = self.inner();
+        own_range
+            .map(move |(first, last)| nodes.range(first..=last))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Iterates over the entries of the range in this tree in descending order.
+    pub fn range_back<R: RangeBounds<K>>(
+        &mut self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (K, V, Option<K>)> + '_ {
+        let own_range = self.own_range(range);
+        let nodes: &AvlTree<K, V> = self.inner();
+        own_range
+            .map(move |(first, last)| nodes.range_back(first..=last))
+            .into_iter()
+            .flatten()
+    }
+
+    // PRIVATE METHODS
+
+    /// Return the shared tree with the root of this tree, public in crate for the health checking.
+    pub(crate) fn inner(&mut self) -> &mut AvlTree<K, V> {
+        self.nodes.root = self.state.root.clone();
+        self.nodes
+    }
+
+    /// Takes the node of the key out of this tree, it stays in the shared store.
+    /// Returns `false` if the key is not in this tree.
+    fn unlink(&mut self, key: &K) -> bool {
+        if !self.contains_key(key) {
+            return false;
+        }
+        self.inner().unlink_without_flush(key).or_panic();
+        self.nodes.flush_cache().or_panic();
+        self.state.root = self.nodes.root.clone();
+        self.refresh_end_of(key);
+        true
+    }
+
+    /// Adds a node of the shared store that is in no tree to this tree.
+    fn link(&mut self, key: K) {
+        self.inner().link_without_flush(key.clone()).or_panic();
+        self.nodes.flush_cache().or_panic();
+        self.state.root = self.nodes.root.clone();
+        if self.state.head.as_ref().map_or(true, |head| key < *head) {
+            self.state.head = Some(key.clone());
+        }
+        if self.state.tail.as_ref().map_or(true, |tail| key > *tail) {
+            self.state.tail = Some(key);
+        }
+    }
+
+    /// Cuts the entries of the range out of this tree, they stay in the shared store as a detached tree.
+    /// Returns the first and last key of the range and the root of the detached tree, `None` if the range is empty.
+    fn cut_range<R: RangeBounds<K>>(&mut self, range: R) -> Option<(K, K, Option<K>)> {
+        let (first, last) = self.own_range(range)?;
+        let (first, last, (cut_root, _)) = self
+            .inner()
+            .cut_range_without_flush(first..=last)
+            .or_panic()?;
+        self.nodes.flush_cache().or_panic();
+        self.state.root = self.nodes.root.clone();
+        self.refresh_ends();
+        Some((first, last, cut_root))
+    }
+
+    /// Adds the detached tree with the keys from `first` to `last` to this tree.
+    /// It is joined in `O(log n)` if the keys do not overlap with the keys of this tree,
+    /// otherwise its nodes are linked one by one.
+    fn attach(&mut self, first: K, last: K, cut_root: Option<K>) {
+        let before = self.state.tail.as_ref().map_or(true, |tail| *tail < first);
+        let after = self.state.head.as_ref().map_or(true, |head| last < *head);
+        if before || after {
+            self.inner().append_without_flush(cut_root).or_panic();
+        } else {
+            let mut current = Some(first);
+            while let Some(key) = current {
+                // The detached tree is not changed by linking its nodes, so its next pointers stay valid.
+                current = match key == last {
+                    true => None,
+                    false => self
+                        .nodes
+                        .store
+                        .get(&key)
+                        .and_then(|node| node.next.clone()),
+                };
+                self.inner().link_without_flush(key).or_panic();
+            }
+        }
+        self.nodes.flush_cache().or_panic();
+        self.state.root = self.nodes.root.clone();
+        self.refresh_ends();
+    }
+
+    /// Returns the first and the last key of this tree inside the range, `None` if the range contains no key of this tree.
+    /// The range can contain keys of other trees, so the bounds are searched from the root of this tree.
+    fn own_range<R: RangeBounds<K>>(&self, range: R) -> Option<(K, K)> {
+        let root = self.state.root.clone();
+        let first = self
+            .nodes
+            .find_first_node(root.clone(), range.start_bound(), Direction::Right)
+            .or_panic()?;
+        let last = self
+            .nodes
+            .find_first_node(root, range.end_bound(), Direction::Left)
+            .or_panic()?;
+        (first <= last).then_some((first, last))
+    }
+
+    /// Panics if the key is stored in the shared store, but not in this tree.
+    fn assert_not_in_other_tree(&self, key: &K) {
+        let stored = self.nodes.get(key).is_some();
+        assert!(
+            !stored || self.contains_key(key),
+            "TreeForest: key {} is in another tree",
+            key
+        );
+    }
+
+    /// Updates the smallest or biggest key after the key was taken out of this tree.
+    fn refresh_end_of(&mut self, key: &K) {
+        if self.state.root.is_none() {
+            self.state.head = None;
+            self.state.tail = None;
+        } else if self.state.head.as_ref() == Some(key) {
+            self.state.head = self.nodes.keys(..).next();
+        } else if self.state.tail.as_ref() == Some(key) {
+            self.state.tail = self.nodes.keys_back(..).next();
+        }
+    }
+
+    /// Takes the root from the shared tree after a change of multiple entries and searches the smallest and biggest key again.
+    fn refresh_ends(&mut self) {
+        self.state.root = self.nodes.root.clone();
+        self.state.head = self.nodes.keys(..).next();
+        self.state.tail = self.nodes.keys_back(..).next();
+    }
+}
+
+impl<K: ScryptoSbor + Eq + Ord + Hash, V: ScryptoSbor> Drop for TreeHandle<'_, K, V> {
+    fn drop(&mut self) {
+        // The root belongs to the state of this tree, the shared tree is stored without a root.
+        self.nodes.root = None;
+    }
+}